@@ -0,0 +1,107 @@
+"""Reference fixture for examples/neural_ca.rs.
+
+Plain-Python port of the growing neural CA update (Mordvintsev et al., 2020) with a periodic
+boundary and every cell firing, run from a single seed with random weights. Writes the weights,
+the initial grid and the grid after STEPS updates as float32 NPY files.
+"""
+
+import random
+import struct
+
+WIDTH, HEIGHT = 12, 12
+CHANNELS, HIDDEN = 8, 16
+STEPS = 6
+ALPHA = 3
+
+rng = random.Random(7)
+
+
+def write_npy(path, shape, values):
+    header = "{'descr': '<f4', 'fortran_order': False, 'shape': (%s), }" % "".join(
+        "%d, " % s for s in shape
+    )
+    header += " " * (63 - (len(header) + 10) % 64) + "\n"
+
+    with open(path, "wb") as f:
+        f.write(b"\x93NUMPY\x01\x00" + struct.pack("<H", len(header)) + header.encode())
+        f.write(struct.pack("<%df" % len(values), *values))
+
+
+def f32(x):
+    return struct.unpack("<f", struct.pack("<f", x))[0]
+
+
+w1 = [[f32(rng.gauss(0, 0.3)) for _ in range(HIDDEN)] for _ in range(3 * CHANNELS)]
+b1 = [f32(rng.gauss(0, 0.1)) for _ in range(HIDDEN)]
+w2 = [[f32(rng.gauss(0, 0.1)) for _ in range(CHANNELS)] for _ in range(HIDDEN)]
+b2 = [f32(rng.gauss(0, 0.01)) for _ in range(CHANNELS)]
+
+grid = [[[0.0] * CHANNELS for _ in range(WIDTH)] for _ in range(HEIGHT)]
+grid[HEIGHT // 2][WIDTH // 2] = [0.0] * ALPHA + [1.0] * (CHANNELS - ALPHA)
+
+initial = [v for row in grid for cell in row for v in cell]
+
+IDENTITY = [[0, 0, 0], [0, 1, 0], [0, 0, 0]]
+SOBEL_X = [[a * b / 8 for b in (-1, 0, 1)] for a in (1, 2, 1)]
+SOBEL_Y = [[SOBEL_X[c][r] for c in range(3)] for r in range(3)]
+
+
+def at(g, y, x):
+    return g[y % HEIGHT][x % WIDTH]
+
+
+def alive(g):
+    return [
+        [
+            max(at(g, y + dy, x + dx)[ALPHA] for dy in (-1, 0, 1) for dx in (-1, 0, 1)) > 0.1
+            for x in range(WIDTH)
+        ]
+        for y in range(HEIGHT)
+    ]
+
+
+def step(g):
+    pre = alive(g)
+    out = [[None] * WIDTH for _ in range(HEIGHT)]
+
+    for y in range(HEIGHT):
+        for x in range(WIDTH):
+            perception = []
+
+            for c in range(CHANNELS):
+                for kernel in (IDENTITY, SOBEL_X, SOBEL_Y):
+                    perception.append(
+                        sum(
+                            kernel[dy + 1][dx + 1] * at(g, y + dy, x + dx)[c]
+                            for dy in (-1, 0, 1)
+                            for dx in (-1, 0, 1)
+                        )
+                    )
+
+            hidden = [
+                max(0.0, sum(p * w1[i][j] for i, p in enumerate(perception)) + b1[j])
+                for j in range(HIDDEN)
+            ]
+
+            out[y][x] = [
+                g[y][x][c] + sum(h * w2[j][c] for j, h in enumerate(hidden)) + b2[c]
+                for c in range(CHANNELS)
+            ]
+
+    post = alive(out)
+
+    return [
+        [out[y][x] if pre[y][x] and post[y][x] else [0.0] * CHANNELS for x in range(WIDTH)]
+        for y in range(HEIGHT)
+    ]
+
+
+for _ in range(STEPS):
+    grid = step(grid)
+
+write_npy("dense1_kernel.npy", (3 * CHANNELS, HIDDEN), [v for row in w1 for v in row])
+write_npy("dense1_bias.npy", (HIDDEN,), b1)
+write_npy("dense2_kernel.npy", (HIDDEN, CHANNELS), [v for row in w2 for v in row])
+write_npy("dense2_bias.npy", (CHANNELS,), b2)
+write_npy("input.npy", (HEIGHT, WIDTH, CHANNELS), initial)
+write_npy("expected.npy", (HEIGHT, WIDTH, CHANNELS), [v for row in grid for cell in row for v in cell])
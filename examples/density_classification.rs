@@ -0,0 +1,48 @@
+use gpca::{
+    dynamics::{context::NodeRng, implementations::neighborhood_table::NeighborhoodTable},
+    system::evolution::{density_classification, Evolution},
+};
+
+fn main() {
+    const RADIUS: usize = 3;
+    const WIDTH: u32 = 79;
+
+    const POPULATION: usize = 40;
+    const GENERATIONS: usize = 30;
+    const SEED: u64 = 2024;
+
+    let mut rng = NodeRng::new(SEED);
+
+    let population = (0..POPULATION)
+        .map(|_| NeighborhoodTable::random(2, 2 * RADIUS, &mut rng))
+        .collect();
+
+    let fitness = |rule: &NeighborhoodTable, seed: u64| {
+        density_classification(rule, WIDTH, 40, 2 * WIDTH as usize, seed)
+    };
+
+    let mut evolution = Evolution::new(population, fitness, SEED);
+
+    for _ in 0..GENERATIONS {
+        let report = evolution.step();
+
+        println!(
+            "generation {:3}  seed {:20}  best {:.2}  mean {:.2}",
+            report.generation, report.evaluation_seed, report.best_fitness, report.mean_fitness
+        );
+    }
+
+    let best = evolution.best().unwrap();
+
+    let table = best
+        .best
+        .table()
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<String>();
+
+    println!(
+        "best rule {table} scored {:.2} on generation {}",
+        best.best_fitness, best.generation
+    );
+}
@@ -0,0 +1,46 @@
+use gpca::{
+    dynamics::implementations::ising::{AcceptanceRule, Ising},
+    spaces::{
+        implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::VON_NEUMANN_NEIGHBORHOOD,
+        },
+        local::Stateable,
+    },
+    system::dynamical_system::DynamicalSystem,
+};
+
+fn main() {
+    const W: u32 = 64;
+    const H: u32 = 64;
+
+    const SWEEPS: usize = 2000;
+    const THERMALIZATION: usize = 500;
+
+    let mem = vec![DiscreteState::from_state(1); (W * H) as usize];
+
+    // the magnetization collapses around Tc = 2 / ln(1 + √2) ≈ 2.269
+    for temperature in [1.5, 2.0, 2.2, 2.3, 2.4, 2.6, 3.0, 3.5] {
+        let space =
+            HyperGraphHeap::new_grid_with_neighborhood(&mem, W, H, &VON_NEUMANN_NEIGHBORHOOD, ());
+        let dynamic = Ising::new(temperature, 0.0, AcceptanceRule::Metropolis);
+
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic.clone()));
+
+        let mut magnetization = 0.0;
+
+        for sweep in 0..SWEEPS {
+            system.compute_checkerboard();
+
+            if sweep >= THERMALIZATION {
+                magnetization += dynamic.magnetization(&system.space_state()).abs();
+            }
+        }
+
+        println!(
+            "T = {temperature:.2}  |m| = {:.3}  e = {:.3}",
+            magnetization / (SWEEPS - THERMALIZATION) as f64,
+            dynamic.energy(system.space()),
+        );
+    }
+}
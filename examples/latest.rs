@@ -5,7 +5,7 @@ use gpca::{
         local::Stateable,
     },
     system::{dynamical_system::DynamicalSystem, utils::generate_image_from_space},
-    third::wgpu::create_gpu_device,
+    third::wgpu::select_device,
 };
 use rand::Rng;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
@@ -18,7 +18,7 @@ async fn main() {
     const STATES: u32 = 4;
     const THRESH: u32 = 2;
 
-    let _device = create_gpu_device().await;
+    let device = select_device().await;
 
     let mem = (0..W * H)
         .into_par_iter()
@@ -32,10 +32,11 @@ async fn main() {
     let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
 
     for _ in 0..500 {
-        system.compute_sync_wgpu(&_device).await;
-        // system.compute_sync();
+        system.compute_sync_on(&device).await.unwrap();
     }
 
+    println!("ran on {:?}", system.last_backend().unwrap());
+
     let img = generate_image_from_space(&system, &|val| {
         let val = ((val.state() * 255) / STATES) as u8;
         image::Rgb([val, val, val])
@@ -0,0 +1,47 @@
+use gpca::{
+    dynamics::implementations::neural_ca::{NeuralCa, NeuralWeights, NpyArray},
+    spaces::{
+        implementations::{basic::HyperGraphHeap, neural::NeuralState},
+        local::Stateable,
+    },
+    system::dynamical_system::DynamicalSystem,
+};
+
+// fixture written by examples/data/neural_ca/generate.py
+const FIXTURE: &str = "examples/data/neural_ca";
+const STEPS: u64 = 6;
+
+fn main() {
+    let weights = NeuralWeights::from_npy_dir(FIXTURE).unwrap();
+
+    // every cell fires so that the run is deterministic
+    let dynamic = NeuralCa::new(weights, 1.0);
+
+    let input = NpyArray::read(format!("{FIXTURE}/input.npy")).unwrap();
+    let expected = NpyArray::read(format!("{FIXTURE}/expected.npy")).unwrap();
+
+    let (h, w) = (input.shape[0] as u32, input.shape[1] as u32);
+
+    let mem = dynamic.grid_from_npy(&input).unwrap();
+    let space = HyperGraphHeap::new_grid(&mem, w, h, ());
+
+    let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
+
+    // one update and one masking pass per step
+    for _ in 0..NeuralCa::PASSES * STEPS {
+        system.compute_sync();
+    }
+
+    let output = system.space_state();
+
+    let error = output
+        .iter()
+        .flat_map(NeuralState::channels)
+        .zip(&expected.data)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, f32::max);
+
+    let alive = output.iter().filter(|n| n.state() == 1).count();
+
+    println!("{alive} live cells after {STEPS} steps, max deviation from the reference {error:e}");
+}
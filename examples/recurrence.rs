@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use gpca::{
+    dynamics::{
+        context::NodeRng,
+        implementations::{life::LifeLike, second_order::SecondOrder},
+    },
+    spaces::{
+        implementations::basic::{DiscreteState, HyperGraphHeap},
+        local::Stateable,
+    },
+    system::dynamical_system::DynamicalSystem,
+};
+
+fn main() {
+    const W: u32 = 4;
+    const H: u32 = 4;
+
+    const MAX_STEPS: u64 = 1_000_000;
+
+    let mut rng = NodeRng::new(7);
+
+    let mem = (0..W * H)
+        .map(|_| DiscreteState::from_state(rng.gen_bool(0.5) as u32))
+        .collect::<Vec<DiscreteState>>();
+
+    let space = HyperGraphHeap::new_grid(&mem, W, H, ());
+    let dynamic = SecondOrder::new(LifeLike::new(&[3], &[2, 3]));
+
+    let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
+    system.set_previous_state(mem.clone());
+
+    // a reversible system on a finite space can only revisit its initial pair of generations
+    let initial = (mem.clone(), mem.clone());
+    let mut seen = HashMap::new();
+
+    while system.generation() < MAX_STEPS {
+        system.compute_sync();
+
+        let pair = (
+            system.previous_state().unwrap().clone(),
+            system.space_state(),
+        );
+
+        if pair == initial {
+            println!(
+                "returned to the initial state after {} steps",
+                system.generation()
+            );
+            break;
+        }
+
+        if let Some(first) = seen.insert(pair, system.generation()) {
+            panic!("generation {first} repeated without passing through the start");
+        }
+    }
+
+    let steps = system.generation();
+
+    for _ in 0..steps {
+        system.compute_sync_backward();
+    }
+
+    assert_eq!(system.space_state(), mem);
+    println!("{steps} backward steps lead back to the initial state");
+}
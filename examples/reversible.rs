@@ -0,0 +1,57 @@
+use gpca::{
+    dynamics::{
+        context::NodeRng,
+        implementations::partitioned::{BlockTable, Partitioned},
+    },
+    spaces::{
+        implementations::basic::{DiscreteState, HyperGraphHeap},
+        local::Stateable,
+    },
+    system::dynamical_system::DynamicalSystem,
+};
+
+fn main() {
+    const W: u32 = 128;
+    const H: u32 = 128;
+
+    const STEPS: usize = 500;
+
+    let mut rng = NodeRng::new(42);
+
+    let mem = (0..W * H)
+        .map(|_| DiscreteState::from_state(rng.gen_bool(0.2) as u32))
+        .collect::<Vec<DiscreteState>>();
+
+    for (name, rule) in [
+        ("critters", BlockTable::critters()),
+        ("tron", BlockTable::tron()),
+        ("billiard ball", BlockTable::billiard_ball()),
+    ] {
+        let forward = Partitioned::new(rule, W);
+        let backward = forward.inverse().expect("reversible block rule");
+
+        let space = HyperGraphHeap::new_grid(&mem, W, H, ());
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(forward));
+
+        for _ in 0..STEPS {
+            system.compute_sync();
+        }
+
+        let changed = system
+            .space_state()
+            .iter()
+            .zip(&mem)
+            .filter(|(a, b)| a != b)
+            .count();
+
+        system.set_dynamic(Box::new(backward));
+
+        for _ in 0..STEPS {
+            system.compute_sync();
+        }
+
+        let restored = system.space_state() == mem;
+
+        println!("{name}: {changed} cells differed after {STEPS} steps, restored: {restored}");
+    }
+}
@@ -0,0 +1,392 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::{context::UpdateContext, local::LocalDynamic},
+    spaces::local::{HyperEdge, Stateable},
+};
+
+/// Period of two rules run together, the least common multiple of theirs.
+fn joint_period(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    let (a, b) = (a?, b?);
+    let (mut x, mut y) = (a, b);
+
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+
+    Some(a / x * b)
+}
+
+/// Runs `first` on even generations and `second` on odd ones.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Alternating<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Alternating<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<N, E, A, B> LocalDynamic<N, E> for Alternating<A, B>
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+    A: LocalDynamic<N, E>,
+    B: LocalDynamic<N, E>,
+{
+    fn states(&self) -> u32 {
+        self.first.states().max(self.second.states())
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        node: &N,
+        nodes: &[N],
+        edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        if context.generation & 1 == 0 {
+            self.first.update_with_context(node, nodes, edges, context)
+        } else {
+            self.second.update_with_context(node, nodes, edges, context)
+        }
+    }
+
+    fn is_second_order(&self) -> bool {
+        self.first.is_second_order() || self.second.is_second_order()
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        self.first.accepts(neighbors) && self.second.accepts(neighbors)
+    }
+
+    fn reads_grid_layout(&self) -> bool {
+        self.first.reads_grid_layout() || self.second.reads_grid_layout()
+    }
+
+    fn accepts_grid(&self, width: u32, height: u32) -> bool {
+        self.first.accepts_grid(width, height) && self.second.accepts_grid(width, height)
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        joint_period(
+            Some(2),
+            joint_period(
+                self.first.generation_period(),
+                self.second.generation_period(),
+            ),
+        )
+    }
+}
+
+/// Applies `first` and then `second` to each node within one step. `second` sees the node as
+/// `first` left it but its neighbors as they were, since their own updates are not known yet;
+/// for `second` to see every node updated, alternate the two over consecutive steps instead.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sequential<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Sequential<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<N, E, A, B> LocalDynamic<N, E> for Sequential<A, B>
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+    A: LocalDynamic<N, E>,
+    B: LocalDynamic<N, E>,
+{
+    fn states(&self) -> u32 {
+        self.first.states().max(self.second.states())
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        node: &N,
+        nodes: &[N],
+        edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        let intermediate = self
+            .first
+            .update_with_context(node, nodes, edges.clone(), context);
+
+        self.second
+            .update_with_context(&intermediate, nodes, edges, context)
+    }
+
+    fn is_second_order(&self) -> bool {
+        self.first.is_second_order() || self.second.is_second_order()
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        self.first.accepts(neighbors) && self.second.accepts(neighbors)
+    }
+
+    fn reads_grid_layout(&self) -> bool {
+        self.first.reads_grid_layout() || self.second.reads_grid_layout()
+    }
+
+    fn accepts_grid(&self, width: u32, height: u32) -> bool {
+        self.first.accepts_grid(width, height) && self.second.accepts_grid(width, height)
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        joint_period(
+            self.first.generation_period(),
+            self.second.generation_period(),
+        )
+    }
+}
+
+/// Runs `inside` on the nodes whose index is set in `mask` and `outside` everywhere else,
+/// including past the end of the mask.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Masked<A, B> {
+    mask: Vec<bool>,
+    inside: A,
+    outside: B,
+}
+
+impl<A, B> Masked<A, B> {
+    pub fn new(mask: Vec<bool>, inside: A, outside: B) -> Self {
+        Self {
+            mask,
+            inside,
+            outside,
+        }
+    }
+
+    pub fn mask(&self) -> &[bool] {
+        &self.mask
+    }
+}
+
+impl<N, E, A, B> LocalDynamic<N, E> for Masked<A, B>
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+    A: LocalDynamic<N, E>,
+    B: LocalDynamic<N, E>,
+{
+    fn states(&self) -> u32 {
+        self.inside.states().max(self.outside.states())
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        node: &N,
+        nodes: &[N],
+        edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        if self.mask.get(context.index).copied().unwrap_or(false) {
+            self.inside.update_with_context(node, nodes, edges, context)
+        } else {
+            self.outside
+                .update_with_context(node, nodes, edges, context)
+        }
+    }
+
+    fn is_second_order(&self) -> bool {
+        self.inside.is_second_order() || self.outside.is_second_order()
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        self.inside.accepts(neighbors) && self.outside.accepts(neighbors)
+    }
+
+    fn reads_grid_layout(&self) -> bool {
+        self.inside.reads_grid_layout() || self.outside.reads_grid_layout()
+    }
+
+    fn accepts_grid(&self, width: u32, height: u32) -> bool {
+        self.inside.accepts_grid(width, height) && self.outside.accepts_grid(width, height)
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        joint_period(
+            self.inside.generation_period(),
+            self.outside.generation_period(),
+        )
+    }
+}
+
+/// Runs `first` with probability `probability` and `second` otherwise, drawn per node and step
+/// from the node's random stream.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mixture<A, B> {
+    probability: f64,
+    first: A,
+    second: B,
+}
+
+impl<A, B> Mixture<A, B> {
+    pub fn new(probability: f64, first: A, second: B) -> Self {
+        Self {
+            probability,
+            first,
+            second,
+        }
+    }
+}
+
+impl<N, E, A, B> LocalDynamic<N, E> for Mixture<A, B>
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+    A: LocalDynamic<N, E>,
+    B: LocalDynamic<N, E>,
+{
+    fn states(&self) -> u32 {
+        self.first.states().max(self.second.states())
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        node: &N,
+        nodes: &[N],
+        edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        if context.rng.gen_bool(self.probability) {
+            self.first.update_with_context(node, nodes, edges, context)
+        } else {
+            self.second.update_with_context(node, nodes, edges, context)
+        }
+    }
+
+    fn is_second_order(&self) -> bool {
+        self.first.is_second_order() || self.second.is_second_order()
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        self.first.accepts(neighbors) && self.second.accepts(neighbors)
+    }
+
+    fn reads_grid_layout(&self) -> bool {
+        self.first.reads_grid_layout() || self.second.reads_grid_layout()
+    }
+
+    fn accepts_grid(&self, width: u32, height: u32) -> bool {
+        self.first.accepts_grid(width, height) && self.second.accepts_grid(width, height)
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamics::implementations::function::FnDynamic,
+        spaces::implementations::basic::DiscreteState,
+    };
+
+    fn one(_: &DiscreteState, _: &[DiscreteState], _: Vec<&HyperEdge<()>>) -> DiscreteState {
+        DiscreteState::from_state(1)
+    }
+
+    fn two(_: &DiscreteState, _: &[DiscreteState], _: Vec<&HyperEdge<()>>) -> DiscreteState {
+        DiscreteState::from_state(2)
+    }
+
+    /// State `dynamic` gives node `index` at `generation`.
+    fn run<D: LocalDynamic<DiscreteState, ()>>(
+        dynamic: &D,
+        seed: u64,
+        index: usize,
+        generation: u64,
+    ) -> u32 {
+        let node = DiscreteState::from_state(0);
+        let mut context = UpdateContext::new(seed, index, generation);
+
+        dynamic
+            .update_with_context(&node, &[], vec![], &mut context)
+            .state()
+    }
+
+    #[test]
+    fn masks_pick_inside_only_at_set_indices() {
+        let masked = Masked::new(
+            vec![true, false, true, false],
+            FnDynamic::new(3, one),
+            FnDynamic::new(3, two),
+        );
+
+        // indices past the end of the mask are outside
+        let states = (0..8).map(|i| run(&masked, 0, i, 0)).collect::<Vec<u32>>();
+
+        assert_eq!(states, [1, 2, 1, 2, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn alternation_follows_generation_parity() {
+        let alternating = Alternating::new(FnDynamic::new(3, one), FnDynamic::new(3, two));
+
+        let states = (0..6)
+            .map(|g| run(&alternating, 0, 3, g))
+            .collect::<Vec<u32>>();
+
+        assert_eq!(states, [1, 2, 1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn certain_mixtures_always_pick_the_same_rule() {
+        let first = Mixture::new(1.0, FnDynamic::new(3, one), FnDynamic::new(3, two));
+        let second = Mixture::new(0.0, FnDynamic::new(3, one), FnDynamic::new(3, two));
+
+        for seed in 0..4 {
+            for index in 0..16 {
+                for generation in 0..16 {
+                    assert_eq!(run(&first, seed, index, generation), 1);
+                    assert_eq!(run(&second, seed, index, generation), 2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sequences_feed_the_first_result_to_the_second() {
+        let increment = |node: &DiscreteState, _: &[DiscreteState], _: Vec<&HyperEdge<()>>| {
+            DiscreteState::from_state(node.state() + 1)
+        };
+        let double = |node: &DiscreteState, _: &[DiscreteState], _: Vec<&HyperEdge<()>>| {
+            DiscreteState::from_state(node.state() * 2)
+        };
+
+        let sequential = Sequential::new(FnDynamic::new(8, increment), FnDynamic::new(8, double));
+
+        assert_eq!(run(&sequential, 0, 0, 0), 2);
+    }
+}
@@ -0,0 +1,86 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::local::LocalDynamic,
+    spaces::local::{HyperEdge, Stateable},
+};
+
+pub const OFF: u32 = 0;
+pub const FIRING: u32 = 1;
+pub const DYING: u32 = 2;
+
+#[derive(Clone, Hash, Eq, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BriansBrain;
+
+impl BriansBrain {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for BriansBrain
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        3
+    }
+
+    fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
+        match node.state() {
+            FIRING => N::from_state(DYING),
+            DYING => N::from_state(OFF),
+            _ => {
+                let firing = nodes.iter().filter(|n| n.state() == FIRING).count();
+
+                if firing == 2 {
+                    N::from_state(FIRING)
+                } else {
+                    node.clone()
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        spaces::implementations::basic::{DiscreteState, HyperGraphHeap},
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    #[test]
+    fn firing_pairs_ignite_the_cells_beside_both() {
+        let side = 8;
+        let mut nodes = vec![DiscreteState::from_state(OFF); side * side];
+        nodes[3 * side + 3] = DiscreteState::from_state(FIRING);
+        nodes[3 * side + 4] = DiscreteState::from_state(FIRING);
+
+        let space = HyperGraphHeap::new_grid(&nodes, side as u32, side as u32, ());
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(BriansBrain::new()));
+        system.compute_sync();
+
+        let states = system
+            .space_state()
+            .iter()
+            .map(|n| n.state())
+            .collect::<Vec<u32>>();
+
+        let mut expected = vec![OFF; side * side];
+        expected[3 * side + 3] = DYING;
+        expected[3 * side + 4] = DYING;
+        for cell in [2 * side + 3, 2 * side + 4, 4 * side + 3, 4 * side + 4] {
+            expected[cell] = FIRING;
+        }
+
+        assert_eq!(states, expected);
+
+        // dying cells always turn off, whatever fires around them
+        system.compute_sync();
+        assert_eq!(system.space_state()[3 * side + 3].state(), OFF);
+    }
+}
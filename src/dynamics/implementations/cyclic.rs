@@ -1,19 +1,14 @@
-use std::{borrow::Cow, fmt::Debug, hash::Hash};
-
-use wgpu::ShaderSource;
+use std::hash::Hash;
 
 use crate::{
     dynamics::local::LocalDynamic,
-    spaces::{
-        implementations::basic::{DiscreteState, HyperGraphHeap},
-        lattice::LatticeComputable,
-        local::{HyperEdge, Stateable},
-    },
-    system::dynamical_system::DynamicalSystem,
-    third::wgpu::{GpuDevice, Image, Kernel, Real},
+    spaces::local::{HyperEdge, Stateable},
+    system::backend::BackendKind,
+    third::codegen::{GridKernel, WgslRule},
 };
 
 #[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CyclicAutomaton {
     states: u32,
     threshold: u32,
@@ -35,6 +30,10 @@ where
         self.states
     }
 
+    fn supports(&self, _backend: BackendKind) -> bool {
+        true
+    }
+
     fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
         let next_state = (node.state() + 1) % self.states;
 
@@ -52,130 +51,27 @@ where
     }
 }
 
-impl<N, E> LatticeComputable<N, E>
-    for DynamicalSystem<
-        HyperGraphHeap<DiscreteState, (), (u32, u32)>,
-        CyclicAutomaton,
-        DiscreteState,
-        (),
-    >
-where
-    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
-    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
-{
-    fn shape(&self) -> Vec<usize> {
-        let (w, h) = self.space().payload();
-
-        vec![*w as usize, *h as usize]
-    }
-
-    fn observation_neighbors(&self) -> Vec<Vec<i32>> {
-        vec![
-            vec![-1, -1],
-            vec![-1, 0],
-            vec![-1, 1],
-            vec![0, -1],
-            vec![0, 1],
-            vec![1, -1],
-            vec![1, 0],
-            vec![1, 1],
-        ]
-    }
-
-    fn update_wgsl_code(&self) -> ShaderSource {
-        wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("cyclic.wgsl")))
-    }
+/// Counts the neighbors in the successor state and advances once there are `threshold` of them.
+impl GridKernel for CyclicAutomaton {
+    fn kernel_rule(&self, offsets: &[(i32, i32)]) -> (u32, WgslRule) {
+        let targets = (0..self.states)
+            .map(|state| (state + 1) % self.states)
+            .collect::<Vec<u32>>();
 
-    fn wgsl_compute(
-        &self,
-        kernel: &Kernel,
-        image: &Image,
-        device: &GpuDevice,
-        shader: wgpu::ShaderSource,
-    ) -> (
-        Image,
-        u64,
-        wgpu::Buffer,
-        wgpu::Buffer,
-        wgpu::BindGroup,
-        wgpu::ComputePipeline,
-    ) {
-        let crop = kernel.size - 1;
-
-        let output = Image {
-            data: Vec::new(),
-            width: image.width - crop,
-            height: image.height - crop,
-        };
-
-        let observation = <_ as LatticeComputable<N, E>>::observation_neighbors(self);
-
-        let neighbors = observation.iter().flatten().copied().collect::<Vec<i32>>();
-
-        let automaton_states = self.dynamic().states;
-        let automaton_threshold = self.dynamic().threshold;
-
-        let output_size =
-            (output.width * output.height * std::mem::size_of::<Real>() as u32) as u64;
-        let params = vec![
-            image.width,
-            image.height,
-            automaton_states,
-            automaton_threshold,
-        ];
-        let params_data = bytemuck::cast_slice(&params);
-
-        // create input and output buffers
-        let input_buffer = device.create_data_buffer("input", bytemuck::cast_slice(&image.data));
-        let result_buffer = device.create_buffer("result", output_size);
-        // let neighbors_buffer = device.create_data_buffer("neighbors", bytemuck::cast_slice(&n));
-        let neighbors_buffer =
-            device.create_data_buffer("neighbors", bytemuck::cast_slice(&neighbors[..]));
-        let output_buffer = device.create_output_buffer("output", output_size);
-
-        // let rule_data = vec![b_num, s_num];
-
-        // let rule_buffer = device.create_data_buffer("rule", bytemuck::cast_slice(&rule_data));
-        let params_buffer = device.create_uniform_buffer("params", params_data);
-
-        // create bind group and compute pipeline
-        let (bind_group, compute_pipeline) = device.create_compute_pipeline(
-            &[
-                (
-                    &input_buffer,
-                    4,
-                    wgpu::BufferBindingType::Storage { read_only: false },
-                ),
-                (
-                    &result_buffer,
-                    4,
-                    wgpu::BufferBindingType::Storage { read_only: false },
-                ),
-                (
-                    &neighbors_buffer,
-                    8,
-                    wgpu::BufferBindingType::Storage { read_only: false },
-                ),
-                (
-                    &params_buffer,
-                    params_data.len() as u64,
-                    wgpu::BufferBindingType::Uniform,
-                ),
-                // (
-                //     &rule_buffer,
-                //     rule_data.len() as u64,
-                //     wgpu::BufferBindingType::Storage { read_only: true },
-                // ),
-            ],
-            shader,
-        );
-        (
-            output,
-            output_size,
-            result_buffer,
-            output_buffer,
-            bind_group,
-            compute_pipeline,
-        )
+        let table = targets
+            .iter()
+            .enumerate()
+            .flat_map(|(state, &next)| {
+                (0..=offsets.len() as u32).map(move |count| {
+                    if count >= self.threshold {
+                        next
+                    } else {
+                        state as u32
+                    }
+                })
+            })
+            .collect();
+
+        (self.states, WgslRule::Counting { targets, table })
     }
 }
@@ -0,0 +1,104 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::{context::UpdateContext, local::LocalDynamic},
+    spaces::local::{HyperEdge, Stateable},
+};
+
+/// Directed percolation on any space: activity spreads from the active neighbors of the previous
+/// generation, either through each of them independently (bond) or once per site (site).
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectedPercolation {
+    p: f64,
+    bond: bool,
+}
+
+impl DirectedPercolation {
+    pub fn site(p: f64) -> Self {
+        Self { p, bond: false }
+    }
+
+    pub fn bond(p: f64) -> Self {
+        Self { p, bond: true }
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for DirectedPercolation
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        2
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        None
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        _node: &N,
+        nodes: &[N],
+        _edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        let active = nodes.iter().filter(|n| n.state() != 0).count() as i32;
+
+        let p = if self.bond {
+            1.0 - (1.0 - self.p).powi(active)
+        } else if active > 0 {
+            self.p
+        } else {
+            0.0
+        };
+
+        N::from_state(context.rng.gen_bool(p) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        spaces::implementations::basic::{DiscreteState, HyperGraphHeap},
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    /// States of a 8 by 8 Moore grid with node 0 active after `steps` steps of `rule`.
+    fn run(rule: DirectedPercolation, seed: u64, steps: usize) -> Vec<u32> {
+        let mut nodes = vec![DiscreteState::from_state(0); 64];
+        nodes[0] = DiscreteState::from_state(1);
+
+        let space = HyperGraphHeap::new_grid(&nodes, 8, 8, ());
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(rule));
+        system.set_seed(seed);
+
+        for _ in 0..steps {
+            system.compute_sync();
+        }
+
+        system.space_state().iter().map(|n| n.state()).collect()
+    }
+
+    #[test]
+    fn extreme_probabilities_die_out_or_fill() {
+        for rule in [DirectedPercolation::site, DirectedPercolation::bond] {
+            assert_eq!(run(rule(0.0), 0, 1), vec![0; 64]);
+            assert_eq!(run(rule(1.0), 0, 8), vec![1; 64]);
+        }
+    }
+
+    #[test]
+    fn seeds_reproduce_runs() {
+        for rule in [DirectedPercolation::site, DirectedPercolation::bond] {
+            assert_eq!(run(rule(0.3), 5, 10), run(rule(0.3), 5, 10));
+            assert_ne!(run(rule(0.3), 5, 10), run(rule(0.3), 6, 10));
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::{context::UpdateContext, local::LocalDynamic},
+    spaces::local::{HyperEdge, Stateable},
+};
+
+/// Domany–Kinzel cellular automaton, meant for a ring built with `ONE_DIMENSIONAL_NEIGHBORHOOD`:
+/// a site becomes active with probability `p1` when one of its two neighbors is active and with
+/// probability `p2` when both are; the site's own state is ignored.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DomanyKinzel {
+    p1: f64,
+    p2: f64,
+}
+
+impl DomanyKinzel {
+    pub fn new(p1: f64, p2: f64) -> Self {
+        Self { p1, p2 }
+    }
+
+    /// Site directed percolation (`p1 = p2 = p`).
+    pub fn site_percolation(p: f64) -> Self {
+        Self::new(p, p)
+    }
+
+    /// Bond directed percolation (`p1 = p`, `p2 = p(2 - p)`).
+    pub fn bond_percolation(p: f64) -> Self {
+        Self::new(p, p * (2.0 - p))
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for DomanyKinzel
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        2
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        None
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        _node: &N,
+        nodes: &[N],
+        _edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        let active = nodes.iter().filter(|n| n.state() != 0).count();
+
+        let p = match active {
+            0 => 0.0,
+            1 => self.p1,
+            _ => self.p2,
+        };
+
+        N::from_state(context.rng.gen_bool(p) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        spaces::implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::ONE_DIMENSIONAL_NEIGHBORHOOD,
+        },
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    /// States of a ring of `width` sites with site 0 active after `steps` steps of `rule`.
+    fn run(rule: DomanyKinzel, width: u32, seed: u64, steps: usize) -> Vec<u32> {
+        let mut nodes = vec![DiscreteState::from_state(0); width as usize];
+        nodes[0] = DiscreteState::from_state(1);
+
+        let space = HyperGraphHeap::new_grid_with_neighborhood(
+            &nodes,
+            width,
+            1,
+            &ONE_DIMENSIONAL_NEIGHBORHOOD,
+            (),
+        );
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(rule));
+        system.set_seed(seed);
+
+        for _ in 0..steps {
+            system.compute_sync();
+        }
+
+        system.space_state().iter().map(|n| n.state()).collect()
+    }
+
+    #[test]
+    fn extreme_probabilities_die_out_or_fill() {
+        assert_eq!(run(DomanyKinzel::new(0.0, 0.0), 9, 0, 3), vec![0; 9]);
+        // an odd ring, so activity reaches both parities of sites
+        assert_eq!(run(DomanyKinzel::new(1.0, 1.0), 9, 0, 20), vec![1; 9]);
+        assert_eq!(
+            run(DomanyKinzel::bond_percolation(1.0), 9, 0, 20),
+            vec![1; 9]
+        );
+    }
+
+    #[test]
+    fn seeds_reproduce_runs() {
+        let rule = || DomanyKinzel::site_percolation(0.8);
+
+        assert_eq!(run(rule(), 64, 1, 20), run(rule(), 64, 1, 20));
+        assert_ne!(run(rule(), 64, 1, 20), run(rule(), 64, 2, 20));
+    }
+}
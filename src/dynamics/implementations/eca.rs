@@ -1,65 +1,59 @@
-// use std::marker::PhantomData;
-
-// use crate::{
-//     dynamic::Dynamic,
-//     space::{DiscreteSpace, OneDimensional},
-// };
-
-// #[derive(Debug, Clone)]
-// pub struct ElementaryCellularAutomaton<S: DiscreteSpace<1>> {
-//     rule: [u32; 8],
-//     phantom: PhantomData<S>,
-// }
-
-// impl<const X: usize> ElementaryCellularAutomaton<OneDimensional<X>> {
-//     pub fn new(rule: [u32; 8]) -> Self {
-//         Self {
-//             rule,
-//             phantom: PhantomData,
-//         }
-//     }
-
-//     pub fn new_from_number(rule: u32) -> Self {
-//         let mut rule_array = [0; 8];
-
-//         for (i, _) in rule_array.clone().iter_mut().enumerate() {
-//             rule_array[i] = (rule >> i) & 1;
-//         }
-
-//         Self::new(rule_array)
-//     }
-// }
-
-// impl<const X: usize> Dynamic<1, OneDimensional<X>>
-//     for ElementaryCellularAutomaton<OneDimensional<X>>
-// {
-//     fn states(&self) -> u32 {
-//         2
-//     }
-
-//     fn update(&self, input: &[u32]) -> Vec<u32> {
-//         let mut output: Vec<u32> = Vec::new();
-
-//         for i in 0..input.len() {
-//             let left = if i == 0 {
-//                 input[input.len() - 1]
-//             } else {
-//                 input[i - 1]
-//             };
-
-//             let right = if i == input.len() - 1 {
-//                 input[0]
-//             } else {
-//                 input[i + 1]
-//             };
-
-//             let index = (left << 2) | (input[i] << 1) | right;
-
-//             // self.rule
-
-//             output.push(self.rule[index as usize]);
-//         }
-
-//         output
-//     }
-// }
+use std::hash::Hash;
+
+use crate::{
+    dynamics::{local::LocalDynamic, statistics::TransitionRule},
+    error::Error,
+    spaces::local::{HyperEdge, Stateable},
+};
+
+/// Wolfram's elementary cellular automata, run on a grid built with the one-dimensional
+/// neighborhood so that every node sees its left and right neighbors in that order.
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElementaryCellularAutomaton {
+    rule: u8,
+}
+
+impl ElementaryCellularAutomaton {
+    pub fn new(rule: u8) -> Self {
+        Self { rule }
+    }
+
+    pub fn rule(&self) -> u8 {
+        self.rule
+    }
+
+    pub fn next_state(&self, left: u32, center: u32, right: u32) -> u32 {
+        let index = (left << 2) | (center << 1) | right;
+
+        (self.rule as u32 >> index) & 1
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for ElementaryCellularAutomaton
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        2
+    }
+
+    fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
+        N::from_state(self.next_state(nodes[0].state() & 1, node.state() & 1, nodes[1].state() & 1))
+    }
+}
+
+impl TransitionRule for ElementaryCellularAutomaton {
+    fn alphabet(&self) -> u32 {
+        2
+    }
+
+    fn neighborhood_size(&self) -> Result<usize, Error> {
+        Ok(3)
+    }
+
+    fn transition(&self, cells: &[u32]) -> u32 {
+        self.next_state(cells[0], cells[1], cells[2])
+    }
+}
@@ -0,0 +1,93 @@
+use std::{fmt::Debug, hash::Hash};
+
+use crate::{
+    dynamics::local::LocalDynamic,
+    spaces::local::{HyperEdge, Stateable},
+};
+
+/// Dynamic given by a closure over the node, its neighbors and its edges.
+#[derive(Clone)]
+pub struct FnDynamic<F> {
+    states: u32,
+    update: F,
+}
+
+impl<F> FnDynamic<F> {
+    pub fn new(states: u32, update: F) -> Self {
+        Self { states, update }
+    }
+}
+
+impl<F> Debug for FnDynamic<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FnDynamic")
+            .field("states", &self.states)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<N, E, F> LocalDynamic<N, E> for FnDynamic<F>
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+    F: Fn(&N, &[N], Vec<&HyperEdge<E>>) -> N + Send + Sync,
+{
+    fn states(&self) -> u32 {
+        self.states
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        (self.update)(node, nodes, edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        spaces::implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::ONE_DIMENSIONAL_NEIGHBORHOOD,
+        },
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    #[test]
+    fn closures_see_the_node_and_its_neighbors_in_order() {
+        // copy the west neighbor, shifting the row east
+        let shift = FnDynamic::new(
+            2,
+            |_: &DiscreteState, nodes: &[DiscreteState], _: Vec<&HyperEdge<()>>| nodes[0].clone(),
+        );
+
+        let nodes = [1, 1, 0, 0, 1, 0, 0, 0]
+            .map(DiscreteState::from_state)
+            .to_vec();
+        let space = HyperGraphHeap::new_grid_with_neighborhood(
+            &nodes,
+            8,
+            1,
+            &ONE_DIMENSIONAL_NEIGHBORHOOD,
+            (),
+        );
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(shift));
+
+        system.compute_sync();
+
+        assert_eq!(
+            system.space_state(),
+            [0, 1, 1, 0, 0, 1, 0, 0].map(DiscreteState::from_state)
+        );
+    }
+
+    #[test]
+    fn debug_output_shows_the_states() {
+        let dynamic = FnDynamic::new(
+            5,
+            |node: &DiscreteState, _: &[DiscreteState], _: Vec<&HyperEdge<()>>| node.clone(),
+        );
+
+        assert_eq!(LocalDynamic::<DiscreteState, ()>::states(&dynamic), 5);
+        assert_eq!(format!("{dynamic:?}"), "FnDynamic { states: 5, .. }");
+    }
+}
@@ -0,0 +1,175 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::{context::UpdateContext, local::LocalDynamic},
+    spaces::local::{HyperEdge, LocalHyperGraph, Stateable},
+};
+
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AcceptanceRule {
+    Metropolis,
+    HeatBath,
+}
+
+/// Ising model with state 0 as spin -1 and state 1 as spin +1, coupled to every neighbor.
+///
+/// Single-spin rules only sample the Boltzmann distribution when neighbors are not flipped
+/// together, so step it with `compute_checkerboard` or `compute_random_sequential` rather than
+/// `compute_sync`.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ising {
+    temperature: f64,
+    field: f64,
+    coupling: f64,
+    rule: AcceptanceRule,
+}
+
+impl Ising {
+    pub fn new(temperature: f64, field: f64, rule: AcceptanceRule) -> Self {
+        Self {
+            temperature,
+            field,
+            coupling: 1.0,
+            rule,
+        }
+    }
+
+    pub fn with_coupling(mut self, coupling: f64) -> Self {
+        self.coupling = coupling;
+        self
+    }
+
+    pub fn spin(state: u32) -> f64 {
+        if state == 0 {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+
+    /// Mean spin, in `[-1, 1]`.
+    pub fn magnetization<N: Stateable>(&self, nodes: &[N]) -> f64 {
+        nodes.iter().map(|n| Self::spin(n.state())).sum::<f64>() / nodes.len() as f64
+    }
+
+    /// Energy per node, counting each bond once.
+    pub fn energy<N, E>(&self, space: &impl LocalHyperGraph<N, E>) -> f64
+    where
+        N: Clone + Sync + Send + Hash + Eq + Stateable,
+        E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+    {
+        let nodes = space.nodes();
+
+        let (bonds, spins) = nodes.iter().enumerate().fold((0.0, 0.0), |(b, s), (i, n)| {
+            let spin = Self::spin(n.state());
+            let local = space.node_neighbors()[&i]
+                .iter()
+                .map(|&j| Self::spin(nodes[j].state()))
+                .sum::<f64>();
+
+            (b + spin * local, s + spin)
+        });
+
+        (-self.coupling * bonds / 2.0 - self.field * spins) / nodes.len() as f64
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for Ising
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        2
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        None
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        node: &N,
+        nodes: &[N],
+        _edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        let spin = Self::spin(node.state());
+        let local_field =
+            self.coupling * nodes.iter().map(|n| Self::spin(n.state())).sum::<f64>() + self.field;
+
+        let up = match self.rule {
+            AcceptanceRule::Metropolis => {
+                let delta = 2.0 * spin * local_field;
+                let flip = delta <= 0.0 || context.rng.gen_bool((-delta / self.temperature).exp());
+
+                (spin > 0.0) != flip
+            }
+            AcceptanceRule::HeatBath => {
+                let p_up = 1.0 / (1.0 + (-2.0 * local_field / self.temperature).exp());
+
+                context.rng.gen_bool(p_up)
+            }
+        };
+
+        N::from_state(up as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        spaces::implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::VON_NEUMANN_NEIGHBORHOOD,
+        },
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    /// Mean |m| of a 32×32 lattice started fully magnetized, after thermalizing.
+    fn mean_magnetization(temperature: f64) -> f64 {
+        const SIDE: u32 = 32;
+        const SWEEPS: usize = 400;
+        const THERMALIZATION: usize = 100;
+
+        let nodes = vec![DiscreteState::from_state(1); (SIDE * SIDE) as usize];
+        let space = HyperGraphHeap::new_grid_with_neighborhood(
+            &nodes,
+            SIDE,
+            SIDE,
+            &VON_NEUMANN_NEIGHBORHOOD,
+            (),
+        );
+        let dynamic = Ising::new(temperature, 0.0, AcceptanceRule::Metropolis);
+
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic.clone()));
+        let mut total = 0.0;
+
+        for sweep in 0..SWEEPS {
+            system.compute_checkerboard();
+
+            if sweep >= THERMALIZATION {
+                total += dynamic.magnetization(&system.space_state()).abs();
+            }
+        }
+
+        total / (SWEEPS - THERMALIZATION) as f64
+    }
+
+    #[test]
+    fn magnetization_drops_across_the_critical_temperature() {
+        // Tc = 2 / ln(1 + √2) ≈ 2.269
+        let ordered = mean_magnetization(1.5);
+        let disordered = mean_magnetization(3.5);
+
+        assert!(ordered > disordered, "{ordered} <= {disordered}");
+        assert!(ordered > 0.9 && disordered < 0.3, "{ordered}, {disordered}");
+    }
+}
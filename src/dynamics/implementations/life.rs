@@ -1,27 +1,33 @@
-use std::{borrow::Cow, fmt::Debug, hash::Hash};
-
-use wgpu::ShaderSource;
+use std::{borrow::Cow, hash::Hash};
 
 use crate::{
-    dynamics::local::LocalDynamic,
-    spaces::{
-        implementations::basic::{DiscreteState, HyperGraphHeap},
-        lattice::LatticeComputable,
-        local::{HyperEdge, Stateable},
-    },
-    system::dynamical_system::DynamicalSystem,
-    third::wgpu::{GpuDevice, Image, Kernel, Real},
+    dynamics::{local::LocalDynamic, statistics::TransitionRule},
+    error::Error,
+    spaces::local::{HyperEdge, Stateable},
+    system::backend::BackendKind,
+    third::codegen::{GridKernel, WgslRule},
 };
 
 #[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LifeLike {
-    b_list: &'static [u32],
-    s_list: &'static [u32],
+    b_list: Cow<'static, [u32]>,
+    s_list: Cow<'static, [u32]>,
 }
 
 impl LifeLike {
     pub fn new(b_list: &'static [u32], s_list: &'static [u32]) -> Self {
-        Self { b_list, s_list }
+        Self {
+            b_list: Cow::Borrowed(b_list),
+            s_list: Cow::Borrowed(s_list),
+        }
+    }
+
+    pub fn from_lists(b_list: Vec<u32>, s_list: Vec<u32>) -> Self {
+        Self {
+            b_list: Cow::Owned(b_list),
+            s_list: Cow::Owned(s_list),
+        }
     }
 }
 
@@ -35,6 +41,10 @@ where
         2
     }
 
+    fn supports(&self, _backend: BackendKind) -> bool {
+        true
+    }
+
     fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
         let total = nodes.iter().map(|n| n.state()).sum();
 
@@ -49,129 +59,50 @@ where
     }
 }
 
-impl<N, E> LatticeComputable<N, E>
-    for DynamicalSystem<HyperGraphHeap<DiscreteState, (), (u32, u32)>, LifeLike, DiscreteState, ()>
-where
-    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
-    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
-{
-    fn shape(&self) -> Vec<usize> {
-        let (w, h) = self.space().payload();
-
-        vec![*w as usize, *h as usize]
-    }
-
-    fn observation_neighbors(&self) -> Vec<Vec<i32>> {
-        vec![
-            vec![-1, -1],
-            vec![-1, 0],
-            vec![-1, 1],
-            vec![0, -1],
-            vec![0, 1],
-            vec![1, -1],
-            vec![1, 0],
-            vec![1, 1],
-        ]
+/// Moore neighborhood, mirroring `update`.
+impl TransitionRule for LifeLike {
+    fn alphabet(&self) -> u32 {
+        2
     }
 
-    fn update_wgsl_code(&self) -> ShaderSource {
-        wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("lifelike.wgsl")))
+    fn neighborhood_size(&self) -> Result<usize, Error> {
+        Ok(9)
     }
 
-    fn wgsl_compute(
-        &self,
-        kernel: &Kernel,
-        image: &Image,
-        device: &GpuDevice,
-        shader: wgpu::ShaderSource,
-    ) -> (
-        Image,
-        u64,
-        wgpu::Buffer,
-        wgpu::Buffer,
-        wgpu::BindGroup,
-        wgpu::ComputePipeline,
-    ) {
-        let crop = kernel.size - 1;
-
-        let output = Image {
-            data: Vec::new(),
-            width: image.width - crop,
-            height: image.height - crop,
-        };
+    fn transition(&self, cells: &[u32]) -> u32 {
+        let total = cells[1..].iter().sum();
 
-        let observation = <_ as LatticeComputable<N, E>>::observation_neighbors(self);
+        if self.b_list.contains(&total) {
+            1
+        } else if self.s_list.contains(&total) {
+            cells[0]
+        } else {
+            0
+        }
+    }
+}
 
-        let neighbors = observation.iter().flatten().copied().collect::<Vec<i32>>();
+impl GridKernel for LifeLike {
+    fn kernel_rule(&self, offsets: &[(i32, i32)]) -> (u32, WgslRule) {
+        let sums = offsets.len() as u32 + 1;
 
-        let b_num = self
-            .dynamic()
-            .b_list
+        let table = [0, 1]
             .iter()
-            .fold(0, |acc, &b| acc | (1 << b));
+            .flat_map(|&state| {
+                (0..sums).map(move |sum| {
+                    let born = self.b_list.contains(&sum);
+                    let survives = state == 1 && self.s_list.contains(&sum);
+
+                    (born || survives) as u32
+                })
+            })
+            .collect::<Vec<u32>>();
+
+        let rule = WgslRule::OuterTotalistic {
+            sums: sums as usize,
+            table,
+        };
 
-        let s_num = self
-            .dynamic()
-            .s_list
-            .iter()
-            .fold(0, |acc, &s| acc | (1 << s));
-
-        let output_size =
-            (output.width * output.height * std::mem::size_of::<Real>() as u32) as u64;
-        let params = vec![image.width, image.height, 8, b_num, s_num];
-        let params_data = bytemuck::cast_slice(&params);
-
-        // create input and output buffers
-        let input_buffer = device.create_data_buffer("input", bytemuck::cast_slice(&image.data));
-        let result_buffer = device.create_buffer("result", output_size);
-        // let neighbors_buffer = device.create_data_buffer("neighbors", bytemuck::cast_slice(&n));
-        let neighbors_buffer =
-            device.create_data_buffer("neighbors", bytemuck::cast_slice(&neighbors[..]));
-        let output_buffer = device.create_output_buffer("output", output_size);
-
-        // let rule_data = vec![b_num, s_num];
-
-        // let rule_buffer = device.create_data_buffer("rule", bytemuck::cast_slice(&rule_data));
-        let params_buffer = device.create_uniform_buffer("params", params_data);
-
-        // create bind group and compute pipeline
-        let (bind_group, compute_pipeline) = device.create_compute_pipeline(
-            &[
-                (
-                    &input_buffer,
-                    4,
-                    wgpu::BufferBindingType::Storage { read_only: false },
-                ),
-                (
-                    &result_buffer,
-                    4,
-                    wgpu::BufferBindingType::Storage { read_only: false },
-                ),
-                (
-                    &neighbors_buffer,
-                    8,
-                    wgpu::BufferBindingType::Storage { read_only: false },
-                ),
-                (
-                    &params_buffer,
-                    params_data.len() as u64,
-                    wgpu::BufferBindingType::Uniform,
-                ),
-                // (
-                //     &rule_buffer,
-                //     rule_data.len() as u64,
-                //     wgpu::BufferBindingType::Storage { read_only: true },
-                // ),
-            ],
-            shader,
-        );
-        (
-            output,
-            output_size,
-            result_buffer,
-            output_buffer,
-            bind_group,
-            compute_pipeline,
-        )
+        (2, rule)
     }
 }
@@ -0,0 +1,223 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::{context::UpdateContext, local::LocalDynamic},
+    spaces::local::{HyperEdge, Stateable},
+};
+
+/// Each cell takes the most frequent state of its neighborhood, itself included; ties keep the
+/// current state. See `Voter` for the stochastic voter model.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Majority {
+    states: u32,
+    twisted: bool,
+}
+
+impl Majority {
+    pub fn new(states: u32) -> Self {
+        Self {
+            states,
+            twisted: false,
+        }
+    }
+
+    /// Vichniac's binary vote rule (B5678/S45678 on the Moore neighborhood).
+    pub fn vote() -> Self {
+        Self::new(2)
+    }
+
+    /// Binary twisted majority ("anneal", B4678/S35678): the outcome is inverted whenever the
+    /// vote is won by a margin of one, which lets boundaries between domains keep moving.
+    pub fn anneal() -> Self {
+        Self {
+            states: 2,
+            twisted: true,
+        }
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for Majority
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        self.states
+    }
+
+    fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
+        if self.twisted {
+            let ones = nodes
+                .iter()
+                .map(|n| n.state().min(1) as usize)
+                .sum::<usize>()
+                + node.state().min(1) as usize;
+            let total = nodes.len() + 1;
+            let zeros = total - ones;
+
+            let won = if ones.abs_diff(zeros) == 1 {
+                ones < zeros
+            } else {
+                ones > zeros
+            };
+
+            return N::from_state(won as u32);
+        }
+
+        let mut counts = vec![0usize; self.states as usize];
+
+        for n in nodes.iter().chain(std::iter::once(node)) {
+            if let Some(count) = counts.get_mut(n.state() as usize) {
+                *count += 1;
+            }
+        }
+
+        let current = counts.get(node.state() as usize).copied().unwrap_or(0);
+        let (best, &best_count) = counts
+            .iter()
+            .enumerate()
+            .max_by_key(|&(state, count)| (count, std::cmp::Reverse(state)))
+            .unwrap();
+
+        if best_count > current {
+            N::from_state(best as u32)
+        } else {
+            node.clone()
+        }
+    }
+}
+
+/// Stochastic voter model: each cell copies the state of a neighbor drawn uniformly from the
+/// node's stream. Cells without neighbors keep their state.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Voter {
+    states: u32,
+}
+
+impl Voter {
+    pub fn new(states: u32) -> Self {
+        Self { states }
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for Voter
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        self.states
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        None
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        node: &N,
+        nodes: &[N],
+        _edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        if nodes.is_empty() {
+            return node.clone();
+        }
+
+        nodes[context.rng.gen_range(nodes.len() as u32) as usize].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        spaces::{
+            implementations::basic::{DiscreteState, HyperGraphHeap},
+            local::LocalHyperGraph,
+        },
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    type Grid = HyperGraphHeap<DiscreteState, (), (u32, u32)>;
+
+    fn states<D>(system: &DynamicalSystem<Grid, D, DiscreteState, ()>) -> Vec<u32>
+    where
+        D: LocalDynamic<DiscreteState, ()> + std::fmt::Debug + Clone,
+    {
+        system.space_state().iter().map(|n| n.state()).collect()
+    }
+
+    fn grid(states: &[u32], side: u32) -> Grid {
+        let nodes = states
+            .iter()
+            .map(|&state| DiscreteState::from_state(state))
+            .collect();
+
+        HyperGraphHeap::new_grid(&nodes, side, side, ())
+    }
+
+    #[test]
+    fn votes_remove_lone_cells_and_keep_domains() {
+        let mut cells = vec![0; 49];
+        // a lone live cell and a solid 3 by 3 block
+        cells[8] = 1;
+        for y in 3..6 {
+            for x in 3..6 {
+                cells[y * 7 + x] = 1;
+            }
+        }
+
+        let mut system =
+            DynamicalSystem::new(Box::new(grid(&cells, 7)), Box::new(Majority::vote()));
+        system.compute_sync();
+
+        let next = states(&system);
+
+        assert_eq!(next[8], 0);
+        // the center and the edge midpoints of the block hold the vote, its corners lose it
+        assert_eq!([next[32], next[25], next[31], next[33], next[39]], [1; 5]);
+        assert_eq!([next[24], next[26], next[38], next[40]], [0; 4]);
+    }
+
+    #[test]
+    fn annealing_inverts_narrow_wins() {
+        let node = DiscreteState::from_state(1);
+
+        // 5 of 9 live: won by one, so the cell dies
+        let narrow = [1, 1, 1, 1, 0, 0, 0, 0].map(DiscreteState::from_state);
+        let next: DiscreteState =
+            LocalDynamic::<DiscreteState, ()>::update(&Majority::anneal(), &node, &narrow, vec![]);
+        assert_eq!(next.state(), 0);
+
+        // 6 of 9 live: a clear win
+        let clear = [1, 1, 1, 1, 1, 0, 0, 0].map(DiscreteState::from_state);
+        let next: DiscreteState =
+            LocalDynamic::<DiscreteState, ()>::update(&Majority::anneal(), &node, &clear, vec![]);
+        assert_eq!(next.state(), 1);
+    }
+
+    #[test]
+    fn voters_copy_a_neighbor_and_keep_consensus() {
+        let cells = (0..25).map(|i| (i * 7 % 3) as u32).collect::<Vec<u32>>();
+        let mut system = DynamicalSystem::new(Box::new(grid(&cells, 5)), Box::new(Voter::new(3)));
+        system.compute_sync();
+
+        let next = states(&system);
+        for (i, neighbors) in system.space().node_neighbors() {
+            assert!(neighbors.iter().any(|&j| cells[j] == next[*i]));
+        }
+
+        let mut system = DynamicalSystem::new(Box::new(grid(&[2; 25], 5)), Box::new(Voter::new(3)));
+        for _ in 0..10 {
+            system.compute_sync();
+        }
+        assert_eq!(states(&system), vec![2; 25]);
+    }
+}
@@ -1,3 +1,23 @@
+pub mod brians_brain;
 pub mod cyclic;
+pub mod directed_percolation;
+pub mod domany_kinzel;
 pub mod eca;
+pub mod function;
+pub mod greenberg_hastings;
+pub mod ising;
+pub mod lattice_gas;
 pub mod life;
+pub mod majority;
+pub mod neighborhood_table;
+pub mod neural_ca;
+pub mod partitioned;
+pub mod potts;
+pub mod probabilistic_life;
+pub mod rule_table;
+pub mod sandpile;
+pub mod second_order;
+pub mod totalistic;
+pub mod turmite;
+pub mod von_neumann_table;
+pub mod wireworld;
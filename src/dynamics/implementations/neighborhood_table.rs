@@ -0,0 +1,114 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::{context::NodeRng, local::LocalDynamic, statistics::TransitionRule},
+    error::Error,
+    spaces::local::{HyperEdge, Stateable},
+    system::backend::BackendKind,
+    third::codegen::{GridKernel, WgslRule},
+};
+
+/// Full lookup table over every configuration of the node and its `neighbors` ordered
+/// neighbors, the center being the most significant digit in base `states`.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NeighborhoodTable {
+    states: u32,
+    neighbors: usize,
+    table: Vec<u32>,
+}
+
+impl NeighborhoodTable {
+    pub fn new(states: u32, neighbors: usize, table: Vec<u32>) -> Self {
+        assert_eq!(
+            table.len(),
+            states.pow(neighbors as u32 + 1) as usize,
+            "one entry per configuration"
+        );
+        assert!(
+            table.iter().all(|&s| s < states),
+            "table entries must be states"
+        );
+
+        Self {
+            states,
+            neighbors,
+            table,
+        }
+    }
+
+    pub fn random(states: u32, neighbors: usize, rng: &mut NodeRng) -> Self {
+        let len = states.pow(neighbors as u32 + 1);
+
+        Self::new(
+            states,
+            neighbors,
+            (0..len).map(|_| rng.gen_range(states)).collect(),
+        )
+    }
+
+    pub fn neighbors(&self) -> usize {
+        self.neighbors
+    }
+
+    pub fn table(&self) -> &[u32] {
+        &self.table
+    }
+
+    fn index(&self, cells: impl Iterator<Item = u32>) -> usize {
+        cells.fold(0, |acc, cell| acc * self.states as usize + cell as usize)
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for NeighborhoodTable
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        self.states
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        neighbors == self.neighbors
+    }
+
+    fn supports(&self, _backend: BackendKind) -> bool {
+        true
+    }
+
+    fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
+        assert_eq!(nodes.len(), self.neighbors, "neighborhood size mismatch");
+
+        let index =
+            self.index(std::iter::once(node.state()).chain(nodes.iter().map(|n| n.state())));
+
+        N::from_state(self.table[index])
+    }
+}
+
+impl TransitionRule for NeighborhoodTable {
+    fn alphabet(&self) -> u32 {
+        self.states
+    }
+
+    fn neighborhood_size(&self) -> Result<usize, Error> {
+        Ok(self.neighbors + 1)
+    }
+
+    fn transition(&self, cells: &[u32]) -> u32 {
+        self.table[self.index(cells.iter().copied())]
+    }
+}
+
+impl GridKernel for NeighborhoodTable {
+    /// Grids whose neighborhoods are not `neighbors` long fail to generate a kernel, their table
+    /// not matching the neighborhood.
+    fn kernel_rule(&self, _offsets: &[(i32, i32)]) -> (u32, WgslRule) {
+        let rule = WgslRule::Table {
+            table: self.table.clone(),
+        };
+
+        (self.states, rule)
+    }
+}
@@ -0,0 +1,359 @@
+use std::{hash::Hash, path::Path};
+
+use crate::{
+    dynamics::{context::UpdateContext, local::LocalDynamic},
+    error::Error,
+    spaces::{
+        implementations::{
+            grid::MOORE_NEIGHBORHOOD,
+            neural::{NeuralState, ALPHA_CHANNEL},
+        },
+        local::HyperEdge,
+    },
+};
+
+fn format_error(message: impl Into<String>) -> Error {
+    Error::Weights(message.into())
+}
+
+/// Little-endian float array read from a NumPy `.npy` file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NpyArray {
+    pub shape: Vec<usize>,
+    pub data: Vec<f32>,
+}
+
+impl NpyArray {
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::parse(&std::fs::read(path)?)
+    }
+
+    /// Accepts C-ordered `<f4` and `<f8` arrays, doubles being narrowed to `f32`.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 10 || &bytes[..6] != b"\x93NUMPY" {
+            return Err(format_error("missing NPY magic"));
+        }
+
+        let (header_len, header_start) = match bytes[6] {
+            1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+            2 | 3 if bytes.len() >= 12 => (
+                u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize,
+                12,
+            ),
+            version => return Err(format_error(format!("unsupported NPY version {version}"))),
+        };
+
+        let header = bytes
+            .get(header_start..header_start + header_len)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .ok_or_else(|| format_error("truncated NPY header"))?;
+
+        let descr = header_value(header, "descr")
+            .and_then(|v| v.split(['\'', '"']).nth(1))
+            .ok_or_else(|| format_error("missing dtype"))?;
+
+        if header_value(header, "fortran_order").is_some_and(|v| v.starts_with("True")) {
+            return Err(format_error("Fortran-ordered arrays are not supported"));
+        }
+
+        let shape = header_value(header, "shape")
+            .and_then(|v| v.strip_prefix('('))
+            .and_then(|v| v.split(')').next())
+            .ok_or_else(|| format_error("missing shape"))?
+            .split(',')
+            .map(str::trim)
+            .filter(|dim| !dim.is_empty())
+            .map(|dim| dim.parse().map_err(|_| format_error("invalid shape")))
+            .collect::<Result<Vec<usize>, Error>>()?;
+
+        let body = &bytes[header_start + header_len..];
+        let len = shape.iter().product::<usize>();
+
+        let data = match descr {
+            "<f4" => body
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect::<Vec<f32>>(),
+            "<f8" => body
+                .chunks_exact(8)
+                .map(|b| f64::from_le_bytes(b.try_into().unwrap()) as f32)
+                .collect::<Vec<f32>>(),
+            other => return Err(format_error(format!("unsupported dtype {other}"))),
+        };
+
+        if data.len() != len {
+            return Err(format_error("data does not match the shape"));
+        }
+
+        Ok(Self { shape, data })
+    }
+}
+
+fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let start = header.find(&format!("'{key}'"))? + key.len() + 2;
+
+    Some(header[start..].trim_start().strip_prefix(':')?.trim_start())
+}
+
+/// Weights of the per-cell network: a ReLU layer over the perception vector and a linear layer
+/// producing the residual update, kernels stored input-major as Keras does.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NeuralWeights {
+    channels: usize,
+    hidden: usize,
+    dense1_kernel: Vec<f32>,
+    dense1_bias: Vec<f32>,
+    dense2_kernel: Vec<f32>,
+    dense2_bias: Vec<f32>,
+}
+
+impl NeuralWeights {
+    pub fn new(
+        channels: usize,
+        hidden: usize,
+        dense1_kernel: Vec<f32>,
+        dense1_bias: Vec<f32>,
+        dense2_kernel: Vec<f32>,
+        dense2_bias: Vec<f32>,
+    ) -> Result<Self, Error> {
+        if channels <= ALPHA_CHANNEL {
+            return Err(format_error("cells need RGBA channels"));
+        }
+
+        if dense1_kernel.len() != 3 * channels * hidden
+            || dense1_bias.len() != hidden
+            || dense2_kernel.len() != hidden * channels
+            || dense2_bias.len() != channels
+        {
+            return Err(format_error(format!(
+                "layer sizes do not match {channels} channels and {hidden} hidden units"
+            )));
+        }
+
+        Ok(Self {
+            channels,
+            hidden,
+            dense1_kernel,
+            dense1_bias,
+            dense2_kernel,
+            dense2_bias,
+        })
+    }
+
+    /// Reads `dense1_kernel.npy`, `dense1_bias.npy`, `dense2_kernel.npy` and, if present,
+    /// `dense2_bias.npy` from `dir`. Kernels may keep the leading unit dimensions of 1×1
+    /// convolutions.
+    pub fn from_npy_dir(dir: impl AsRef<Path>) -> Result<Self, Error> {
+        let dir = dir.as_ref();
+
+        let dense1_kernel = NpyArray::read(dir.join("dense1_kernel.npy"))?;
+        let dense2_kernel = NpyArray::read(dir.join("dense2_kernel.npy"))?;
+
+        let (hidden, channels) = match dense2_kernel.shape.as_slice() {
+            [.., hidden, channels] => (*hidden, *channels),
+            _ => return Err(format_error("dense2 kernel must be a matrix")),
+        };
+
+        let dense2_bias = match NpyArray::read(dir.join("dense2_bias.npy")) {
+            Ok(bias) => bias.data,
+            Err(Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+                vec![0.0; channels]
+            }
+            Err(err) => return Err(err),
+        };
+
+        Self::new(
+            channels,
+            hidden,
+            dense1_kernel.data,
+            NpyArray::read(dir.join("dense1_bias.npy"))?.data,
+            dense2_kernel.data,
+            dense2_bias,
+        )
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    pub fn hidden(&self) -> usize {
+        self.hidden
+    }
+
+    fn residual(&self, perception: &[f32]) -> Vec<f32> {
+        let hidden = (0..self.hidden)
+            .map(|j| {
+                let sum = perception
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| p * self.dense1_kernel[i * self.hidden + j])
+                    .sum::<f32>();
+
+                (sum + self.dense1_bias[j]).max(0.0)
+            })
+            .collect::<Vec<f32>>();
+
+        (0..self.channels)
+            .map(|c| {
+                hidden
+                    .iter()
+                    .enumerate()
+                    .map(|(j, h)| h * self.dense2_kernel[j * self.channels + c])
+                    .sum::<f32>()
+                    + self.dense2_bias[c]
+            })
+            .collect()
+    }
+}
+
+/// Growing neural cellular automaton (Mordvintsev et al., 2020), inference only, on a Moore grid.
+///
+/// Each cell perceives every channel through the identity and the two Sobel filters, in that
+/// order per channel, feeds the result to the network and, with probability `fire_rate`, adds
+/// the output to its channels. Cells with no alpha above 0.1 in their 3×3 neighborhood, before or
+/// after the update, are then cleared.
+///
+/// The second check needs the neighbors' updated alpha, so one automaton step takes
+/// [`NeuralCa::PASSES`] system steps: even generations update, odd generations clear dead cells.
+/// A system's `generation()` therefore counts passes, and `generation() / NeuralCa::PASSES` is
+/// the number of automaton steps taken.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NeuralCa {
+    weights: NeuralWeights,
+    fire_rate: f64,
+}
+
+impl NeuralCa {
+    /// System steps per automaton step.
+    pub const PASSES: u64 = 2;
+
+    pub fn new(weights: NeuralWeights, fire_rate: f64) -> Self {
+        Self { weights, fire_rate }
+    }
+
+    pub fn weights(&self) -> &NeuralWeights {
+        &self.weights
+    }
+
+    /// The usual starting cell: alpha and hidden channels at 1, color at 0.
+    pub fn seed(&self) -> NeuralState {
+        let channels = (0..self.weights.channels)
+            .map(|c| if c < ALPHA_CHANNEL { 0.0 } else { 1.0 })
+            .collect();
+
+        NeuralState::new(channels)
+    }
+
+    /// Cells read from an `(height, width, channels)` array, row by row.
+    pub fn grid_from_npy(&self, array: &NpyArray) -> Result<Vec<NeuralState>, Error> {
+        match array.shape.as_slice() {
+            [_, _, channels] if *channels == self.weights.channels => Ok(array
+                .data
+                .chunks(*channels)
+                .map(|cell| NeuralState::new(cell.to_vec()))
+                .collect()),
+            _ => Err(format_error("grid must be (height, width, channels)")),
+        }
+    }
+
+    pub fn empty(&self) -> NeuralState {
+        NeuralState::new(vec![0.0; self.weights.channels])
+    }
+
+    fn alive<'a>(cells: impl Iterator<Item = &'a NeuralState>) -> bool {
+        cells.map(|c| c.alpha()).fold(f32::MIN, f32::max) > 0.1
+    }
+
+    fn perceive(&self, node: &NeuralState, nodes: &[NeuralState]) -> Vec<f32> {
+        let mut window = [[node; 3]; 3];
+
+        for ((dx, dy), neighbor) in MOORE_NEIGHBORHOOD.iter().zip(nodes) {
+            window[(dy + 1) as usize][(dx + 1) as usize] = neighbor;
+        }
+
+        let sobel = |along_x: bool, channel: usize| {
+            let mut sum = 0.0;
+
+            for (y, row) in window.iter().enumerate() {
+                for (x, cell) in row.iter().enumerate() {
+                    let (across, along) = if along_x { (y, x) } else { (x, y) };
+                    sum += [1.0, 2.0, 1.0][across] * (along as f32 - 1.0) * cell.channel(channel);
+                }
+            }
+
+            sum / 8.0
+        };
+
+        (0..self.weights.channels)
+            .flat_map(|c| [node.channel(c), sobel(true, c), sobel(false, c)])
+            .collect()
+    }
+}
+
+impl<E> LocalDynamic<NeuralState, E> for NeuralCa
+where
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        2
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        neighbors == MOORE_NEIGHBORHOOD.len()
+    }
+
+    /// Cells fire at random unless they always or never do.
+    fn generation_period(&self) -> Option<u64> {
+        (self.fire_rate <= 0.0 || self.fire_rate >= 1.0).then_some(Self::PASSES)
+    }
+
+    fn update(
+        &self,
+        node: &NeuralState,
+        nodes: &[NeuralState],
+        edges: Vec<&HyperEdge<E>>,
+    ) -> NeuralState {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        node: &NeuralState,
+        nodes: &[NeuralState],
+        _edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> NeuralState {
+        assert_eq!(
+            nodes.len(),
+            MOORE_NEIGHBORHOOD.len(),
+            "neural CA needs a Moore grid"
+        );
+
+        let neighborhood = || std::iter::once(node).chain(nodes);
+
+        if context.generation % Self::PASSES == 1 {
+            return if node.alive_before() && Self::alive(neighborhood()) {
+                NeuralState::new(node.channels().to_vec())
+            } else {
+                self.empty()
+            };
+        }
+
+        let alive = Self::alive(neighborhood());
+        let mut channels = (0..self.weights.channels)
+            .map(|c| node.channel(c))
+            .collect::<Vec<f32>>();
+
+        if context.rng.gen_bool(self.fire_rate) {
+            let residual = self.weights.residual(&self.perceive(node, nodes));
+
+            for (channel, delta) in channels.iter_mut().zip(residual) {
+                *channel += delta;
+            }
+        }
+
+        NeuralState::with_alive_before(channels, alive)
+    }
+}
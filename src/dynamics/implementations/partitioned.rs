@@ -0,0 +1,353 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::{context::UpdateContext, local::LocalDynamic},
+    spaces::{
+        implementations::grid::MOORE_NEIGHBORHOOD,
+        local::{HyperEdge, Stateable},
+    },
+};
+
+/// Rule acting on a whole 2×2 block at once. Blocks are `[top-left, top-right, bottom-left,
+/// bottom-right]`.
+pub trait BlockRule: Clone + Send + Sync {
+    fn states(&self) -> u32;
+    /// Image of `block` on a step using `partition`, see [`Partitioned::partition`]. Most rules
+    /// ignore it; it lets a rule alternate between two block maps.
+    fn apply(&self, block: [u32; 4], partition: u32) -> [u32; 4];
+
+    /// The rule undoing this one, if it is a bijection on blocks.
+    fn inverse(&self) -> Option<Self> {
+        None
+    }
+}
+
+/// Block rule given as a lookup table over every block of a `states`-state automaton.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockTable {
+    states: u32,
+    table: Vec<u32>,
+}
+
+impl BlockTable {
+    /// `table[i]` is the image of block `i`, where a block `[a, b, c, d]` has index
+    /// `a + b·k + c·k² + d·k³` for `k` states.
+    pub fn new(states: u32, table: Vec<u32>) -> Self {
+        assert_eq!(
+            table.len(),
+            states.pow(4) as usize,
+            "block tables need k^4 entries"
+        );
+
+        Self { states, table }
+    }
+
+    pub fn from_fn(states: u32, rule: impl Fn([u32; 4]) -> [u32; 4]) -> Self {
+        let table = (0..states.pow(4))
+            .map(|index| Self::encode(states, rule(Self::decode(states, index))))
+            .collect();
+
+        Self::new(states, table)
+    }
+
+    /// Reversible rule of Toffoli and Margolus: blocks with exactly two live cells are kept, any
+    /// other block is complemented, and blocks that had three live cells are also turned half
+    /// way around.
+    pub fn critters() -> Self {
+        Self::from_fn(2, |block| {
+            let live = block.iter().sum::<u32>();
+            let flipped = block.map(|c| 1 - c);
+
+            match live {
+                2 => block,
+                3 => [flipped[3], flipped[2], flipped[1], flipped[0]],
+                _ => flipped,
+            }
+        })
+    }
+
+    /// Complements uniform blocks and keeps every other block.
+    pub fn tron() -> Self {
+        Self::from_fn(2, |block| match block.iter().sum::<u32>() {
+            0 | 4 => block.map(|c| 1 - c),
+            _ => block,
+        })
+    }
+
+    /// Fredkin's billiard-ball model: a lone ball crosses its block diagonally, two balls meeting
+    /// on a diagonal leave along the other one, and everything else (mirrors) stays put.
+    pub fn billiard_ball() -> Self {
+        Self::from_fn(2, |block| match block {
+            [1, 0, 0, 0] | [0, 1, 0, 0] | [0, 0, 1, 0] | [0, 0, 0, 1] => {
+                [block[3], block[2], block[1], block[0]]
+            }
+            [1, 0, 0, 1] => [0, 1, 1, 0],
+            [0, 1, 1, 0] => [1, 0, 0, 1],
+            other => other,
+        })
+    }
+
+    pub fn is_reversible(&self) -> bool {
+        let mut seen = vec![false; self.table.len()];
+
+        self.table
+            .iter()
+            .all(|&image| !std::mem::replace(&mut seen[image as usize], true))
+    }
+
+    fn encode(states: u32, block: [u32; 4]) -> u32 {
+        block.iter().rev().fold(0, |acc, &cell| acc * states + cell)
+    }
+
+    fn decode(states: u32, index: u32) -> [u32; 4] {
+        [0, 1, 2, 3].map(|i| index / states.pow(i) % states)
+    }
+}
+
+impl BlockRule for BlockTable {
+    fn states(&self) -> u32 {
+        self.states
+    }
+
+    fn apply(&self, block: [u32; 4], _partition: u32) -> [u32; 4] {
+        Self::decode(
+            self.states,
+            self.table[Self::encode(self.states, block) as usize],
+        )
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        if !self.is_reversible() {
+            return None;
+        }
+
+        let mut table = vec![0; self.table.len()];
+
+        for (index, &image) in self.table.iter().enumerate() {
+            table[image as usize] = index as u32;
+        }
+
+        Some(Self::new(self.states, table))
+    }
+}
+
+/// Runs a [`BlockRule`] on the Margolus neighborhood: the grid is split into 2×2 blocks whose
+/// origin alternates between `(0, 0)` and `(1, 1)` from one generation to the next.
+///
+/// Every cell computes its own block, so this is an ordinary local dynamic that steps with
+/// `compute_sync`. It needs a Moore grid (`new_grid`) of the given width, and both sides must be
+/// even for the blocks to tile the torus; a system stepping any other grid reports
+/// `Error::GridSize`.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Partitioned<R> {
+    rule: R,
+    width: u32,
+    phase: u64,
+}
+
+impl<R: BlockRule> Partitioned<R> {
+    pub fn new(rule: R, width: u32) -> Self {
+        Self {
+            rule,
+            width,
+            phase: 0,
+        }
+    }
+
+    pub fn rule(&self) -> &R {
+        &self.rule
+    }
+
+    /// Partition used at `generation`, 0 for blocks at `(0, 0)` and 1 for blocks at `(1, 1)`.
+    pub fn partition(&self, generation: u64) -> u32 {
+        ((generation + self.phase) % 2) as u32
+    }
+
+    /// Dynamic that walks a system back through the generations this one produced. It must take
+    /// over right where this one stopped: each step then undoes the previous forward step with
+    /// the same partition.
+    pub fn inverse(&self) -> Option<Self> {
+        Some(Self {
+            rule: self.rule.inverse()?,
+            width: self.width,
+            phase: self.phase ^ 1,
+        })
+    }
+}
+
+impl<N, E, R> LocalDynamic<N, E> for Partitioned<R>
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+    R: BlockRule,
+{
+    fn states(&self) -> u32 {
+        self.rule.states()
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        neighbors == 8
+    }
+
+    fn reads_grid_layout(&self) -> bool {
+        true
+    }
+
+    fn accepts_grid(&self, width: u32, height: u32) -> bool {
+        width == self.width && width.is_multiple_of(2) && height.is_multiple_of(2)
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        Some(2)
+    }
+
+    /// Partitioned rules find their block from the node index and the generation, which only a
+    /// system step provides. Without them the block is unknown, so the node is kept as it is.
+    fn update(&self, node: &N, _nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
+        node.clone()
+    }
+
+    fn update_with_context(
+        &self,
+        node: &N,
+        nodes: &[N],
+        _edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        assert_eq!(nodes.len(), 8, "partitioned rules need a Moore grid");
+
+        let partition = self.partition(context.generation);
+        let x = context.index % self.width as usize;
+        let y = context.index / self.width as usize;
+
+        // position of this cell inside its block
+        let (cx, cy) = ((x + partition as usize) % 2, (y + partition as usize) % 2);
+
+        let block = [(0, 0), (1, 0), (0, 1), (1, 1)].map(|(bx, by)| {
+            let offset = (bx - cx as i32, by - cy as i32);
+
+            if offset == (0, 0) {
+                node.state()
+            } else {
+                let position = MOORE_NEIGHBORHOOD.iter().position(|&o| o == offset);
+                nodes[position.unwrap()].state()
+            }
+        });
+
+        N::from_state(self.rule.apply(block, partition)[cy * 2 + cx])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamics::{context::NodeRng, implementations::lattice_gas::TmGas},
+        error::Error,
+        spaces::implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::VON_NEUMANN_NEIGHBORHOOD,
+        },
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    #[test]
+    fn update_without_a_step_keeps_the_node() {
+        let node = DiscreteState::from_state(1);
+        let nodes = vec![DiscreteState::from_state(0); 8];
+
+        let next = LocalDynamic::<DiscreteState, ()>::update(
+            &Partitioned::new(TmGas::new(), 4),
+            &node,
+            &nodes,
+            vec![],
+        );
+
+        assert_eq!(next, node);
+    }
+
+    #[test]
+    fn grids_the_blocks_do_not_tile_are_refused() {
+        for (width, height, rule_width) in [(4, 4, 6), (6, 5, 6), (5, 4, 5)] {
+            let nodes = vec![DiscreteState::from_state(0); (width * height) as usize];
+            let space = HyperGraphHeap::new_grid(&nodes, width, height, ());
+            let dynamic = Partitioned::new(TmGas::new(), rule_width);
+            let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
+
+            assert!(
+                matches!(
+                    system.try_compute_sync(),
+                    Err(Error::GridSize { width: w, height: h }) if (w, h) == (width, height)
+                ),
+                "{width}×{height} grid, rule width {rule_width}"
+            );
+            assert_eq!(system.space_state(), nodes);
+        }
+    }
+
+    #[test]
+    fn von_neumann_grids_are_refused() {
+        let nodes = vec![DiscreteState::from_state(0); 16];
+        let space =
+            HyperGraphHeap::new_grid_with_neighborhood(&nodes, 4, 4, &VON_NEUMANN_NEIGHBORHOOD, ());
+        let mut system =
+            DynamicalSystem::new(Box::new(space), Box::new(Partitioned::new(TmGas::new(), 4)));
+
+        assert!(matches!(
+            system.try_compute_sync(),
+            Err(Error::Neighborhood { neighbors: 4, .. })
+        ));
+    }
+
+    /// Steps a random soup forward, then back with the inverse rule.
+    fn retraces<R: BlockRule + std::fmt::Debug + 'static>(name: &str, rule: R) {
+        const SIDE: u32 = 32;
+        const STEPS: usize = 100;
+
+        let mut rng = NodeRng::new(42);
+        let nodes = (0..SIDE * SIDE)
+            .map(|_| DiscreteState::from_state(rng.gen_bool(0.2) as u32))
+            .collect::<Vec<DiscreteState>>();
+
+        let forward = Partitioned::new(rule, SIDE);
+        let backward = forward.inverse().unwrap();
+
+        let space = HyperGraphHeap::new_grid(&nodes, SIDE, SIDE, ());
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(forward));
+
+        for _ in 0..STEPS {
+            system.compute_sync();
+        }
+
+        assert_ne!(system.space_state(), nodes, "{name} did not move");
+
+        system.set_dynamic(Box::new(backward));
+
+        for _ in 0..STEPS {
+            system.compute_sync();
+        }
+
+        assert_eq!(
+            system.space_state(),
+            nodes,
+            "{name} did not retrace its steps"
+        );
+    }
+
+    #[test]
+    fn inverses_retrace_the_forward_steps() {
+        for (name, rule) in [
+            ("critters", BlockTable::critters()),
+            ("tron", BlockTable::tron()),
+            ("billiard ball", BlockTable::billiard_ball()),
+        ] {
+            assert!(rule.is_reversible(), "{name}");
+
+            retraces(name, rule);
+        }
+
+        retraces("tm gas", TmGas::new());
+    }
+}
@@ -0,0 +1,207 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::{context::UpdateContext, local::LocalDynamic},
+    spaces::local::{HyperEdge, LocalHyperGraph, Stateable},
+};
+
+use super::ising::AcceptanceRule;
+
+/// q-state Potts model, `E = -J Σ δ(s_i, s_j) - h Σ δ(s_i, 0)`, with the field favoring state 0.
+///
+/// Like [`super::ising::Ising`], step it with `compute_checkerboard` or
+/// `compute_random_sequential`.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Potts {
+    states: u32,
+    temperature: f64,
+    field: f64,
+    coupling: f64,
+    rule: AcceptanceRule,
+}
+
+impl Potts {
+    pub fn new(states: u32, temperature: f64, field: f64, rule: AcceptanceRule) -> Self {
+        Self {
+            states,
+            temperature,
+            field,
+            coupling: 1.0,
+            rule,
+        }
+    }
+
+    pub fn with_coupling(mut self, coupling: f64) -> Self {
+        self.coupling = coupling;
+        self
+    }
+
+    fn local_energy<N: Stateable>(&self, state: u32, nodes: &[N]) -> f64 {
+        let aligned = nodes.iter().filter(|n| n.state() == state).count() as f64;
+
+        -self.coupling * aligned - self.field * (state == 0) as u32 as f64
+    }
+
+    /// `(q · max fraction - 1) / (q - 1)`: 0 when all states are equally common, 1 when ordered.
+    /// Nodes in states outside the model count towards no state.
+    pub fn magnetization<N: Stateable>(&self, nodes: &[N]) -> f64 {
+        let mut counts = vec![0usize; self.states as usize];
+
+        for n in nodes {
+            if let Some(count) = counts.get_mut(n.state() as usize) {
+                *count += 1;
+            }
+        }
+
+        let max_fraction = counts.iter().max().copied().unwrap_or(0) as f64 / nodes.len() as f64;
+        let q = self.states as f64;
+
+        (q * max_fraction - 1.0) / (q - 1.0)
+    }
+
+    /// Energy per node, counting each bond once.
+    pub fn energy<N, E>(&self, space: &impl LocalHyperGraph<N, E>) -> f64
+    where
+        N: Clone + Sync + Send + Hash + Eq + Stateable,
+        E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+    {
+        let nodes = space.nodes();
+
+        let (bonds, zeros) = nodes.iter().enumerate().fold((0.0, 0.0), |(b, z), (i, n)| {
+            let aligned = space.node_neighbors()[&i]
+                .iter()
+                .filter(|&&j| nodes[j].state() == n.state())
+                .count() as f64;
+
+            (b + aligned, z + (n.state() == 0) as u32 as f64)
+        });
+
+        (-self.coupling * bonds / 2.0 - self.field * zeros) / nodes.len() as f64
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for Potts
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        self.states
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        None
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        node: &N,
+        nodes: &[N],
+        _edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        match self.rule {
+            AcceptanceRule::Metropolis => {
+                let current = node.state();
+                let proposal = (current + 1 + context.rng.gen_range(self.states - 1)) % self.states;
+
+                let delta = self.local_energy(proposal, nodes) - self.local_energy(current, nodes);
+
+                if delta <= 0.0 || context.rng.gen_bool((-delta / self.temperature).exp()) {
+                    N::from_state(proposal)
+                } else {
+                    node.clone()
+                }
+            }
+            AcceptanceRule::HeatBath => {
+                let energies = (0..self.states)
+                    .map(|s| self.local_energy(s, nodes))
+                    .collect::<Vec<f64>>();
+                let lowest = energies.iter().copied().fold(f64::INFINITY, f64::min);
+
+                let weights = energies
+                    .iter()
+                    .map(|e| (-(e - lowest) / self.temperature).exp())
+                    .collect::<Vec<f64>>();
+
+                let mut target = context.rng.next_f64() * weights.iter().sum::<f64>();
+
+                for (state, weight) in weights.iter().enumerate() {
+                    if target < *weight {
+                        return N::from_state(state as u32);
+                    }
+
+                    target -= weight;
+                }
+
+                N::from_state(self.states - 1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        spaces::implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::VON_NEUMANN_NEIGHBORHOOD,
+        },
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    /// Mean magnetization of a 3-state 24×24 lattice started ordered, after thermalizing.
+    fn mean_magnetization(temperature: f64) -> f64 {
+        const SIDE: u32 = 24;
+        const SWEEPS: usize = 300;
+        const THERMALIZATION: usize = 100;
+
+        let nodes = vec![DiscreteState::from_state(0); (SIDE * SIDE) as usize];
+        let space = HyperGraphHeap::new_grid_with_neighborhood(
+            &nodes,
+            SIDE,
+            SIDE,
+            &VON_NEUMANN_NEIGHBORHOOD,
+            (),
+        );
+        let dynamic = Potts::new(3, temperature, 0.0, AcceptanceRule::HeatBath);
+
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic.clone()));
+        let mut total = 0.0;
+
+        for sweep in 0..SWEEPS {
+            system.compute_checkerboard();
+
+            if sweep >= THERMALIZATION {
+                total += dynamic.magnetization(&system.space_state());
+            }
+        }
+
+        total / (SWEEPS - THERMALIZATION) as f64
+    }
+
+    #[test]
+    fn magnetization_drops_across_the_critical_temperature() {
+        // Tc = 1 / ln(1 + √3) ≈ 0.995 for q = 3
+        let ordered = mean_magnetization(0.6);
+        let disordered = mean_magnetization(1.6);
+
+        assert!(ordered > disordered, "{ordered} <= {disordered}");
+        assert!(ordered > 0.8 && disordered < 0.3, "{ordered}, {disordered}");
+    }
+
+    #[test]
+    fn states_outside_the_model_count_towards_none() {
+        let potts = Potts::new(3, 1.0, 0.0, AcceptanceRule::Metropolis);
+        let nodes = [0, 0, 7, 7].map(DiscreteState::from_state);
+
+        // half the nodes in state 0, the others in none of the three
+        assert_eq!(potts.magnetization(&nodes), 0.25);
+    }
+}
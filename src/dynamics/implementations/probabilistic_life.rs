@@ -0,0 +1,118 @@
+use std::{borrow::Cow, hash::Hash};
+
+use crate::{
+    dynamics::{context::UpdateContext, local::LocalDynamic},
+    spaces::local::{HyperEdge, Stateable},
+};
+
+/// Life-like rule where births and survivals allowed by the B/S lists only happen with the given
+/// probabilities.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProbabilisticLife {
+    b_list: Cow<'static, [u32]>,
+    s_list: Cow<'static, [u32]>,
+    birth_probability: f64,
+    survival_probability: f64,
+}
+
+impl ProbabilisticLife {
+    pub fn new(
+        b_list: &'static [u32],
+        s_list: &'static [u32],
+        birth_probability: f64,
+        survival_probability: f64,
+    ) -> Self {
+        Self {
+            b_list: Cow::Borrowed(b_list),
+            s_list: Cow::Borrowed(s_list),
+            birth_probability,
+            survival_probability,
+        }
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for ProbabilisticLife
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        2
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        None
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        node: &N,
+        nodes: &[N],
+        _edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        let total = nodes.iter().map(|n| n.state()).sum();
+
+        let alive = if node.state() == 0 {
+            self.b_list.contains(&total) && context.rng.gen_bool(self.birth_probability)
+        } else {
+            self.s_list.contains(&total) && context.rng.gen_bool(self.survival_probability)
+        };
+
+        N::from_state(alive as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamics::{context::NodeRng, implementations::life::LifeLike},
+        spaces::implementations::basic::{DiscreteState, HyperGraphHeap},
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    fn soup() -> Vec<DiscreteState> {
+        let mut rng = NodeRng::new(42);
+
+        (0..256)
+            .map(|_| DiscreteState::from_state(rng.gen_bool(0.4) as u32))
+            .collect()
+    }
+
+    fn run<D>(rule: D, seed: u64, steps: usize) -> Vec<u32>
+    where
+        D: LocalDynamic<DiscreteState, ()> + std::fmt::Debug + Clone,
+    {
+        let space = HyperGraphHeap::new_grid(&soup(), 16, 16, ());
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(rule));
+        system.set_seed(seed);
+
+        for _ in 0..steps {
+            system.compute_sync();
+        }
+
+        system.space_state().iter().map(|n| n.state()).collect()
+    }
+
+    #[test]
+    fn certain_births_and_survivals_are_life() {
+        assert_eq!(
+            run(ProbabilisticLife::new(&[3], &[2, 3], 1.0, 1.0), 9, 10),
+            run(LifeLike::new(&[3], &[2, 3]), 0, 10)
+        );
+    }
+
+    #[test]
+    fn seeds_reproduce_runs() {
+        let rule = || ProbabilisticLife::new(&[3], &[2, 3], 0.7, 0.9);
+
+        assert_eq!(run(rule(), 1, 10), run(rule(), 1, 10));
+        assert_ne!(run(rule(), 1, 10), run(rule(), 2, 10));
+    }
+}
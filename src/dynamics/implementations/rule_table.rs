@@ -0,0 +1,905 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    path::Path,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    dynamics::{local::LocalDynamic, statistics::TransitionRule},
+    error::Error,
+    spaces::{
+        implementations::grid::{grid_neighborhood, neighborhood_positions},
+        local::{HyperEdge, Stateable},
+    },
+};
+
+fn parse_error(line: usize, message: impl Into<String>) -> Error {
+    Error::RuleParse {
+        line,
+        message: message.into(),
+    }
+}
+
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RuleNeighborhood {
+    Moore,
+    VonNeumann,
+    Hexagonal,
+    OneDimensional,
+}
+
+impl RuleNeighborhood {
+    /// Neighbor offsets in Golly's rule table order, clockwise from the north.
+    pub fn offsets(&self) -> &'static [(i32, i32)] {
+        match self {
+            RuleNeighborhood::Moore => &[
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1),
+            ],
+            RuleNeighborhood::VonNeumann => &[(0, -1), (1, 0), (0, 1), (-1, 0)],
+            RuleNeighborhood::Hexagonal => &[(0, -1), (1, 0), (1, 1), (0, 1), (-1, 0), (-1, -1)],
+            RuleNeighborhood::OneDimensional => &[(-1, 0), (1, 0)],
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Moore" => Some(RuleNeighborhood::Moore),
+            "vonNeumann" => Some(RuleNeighborhood::VonNeumann),
+            "hexagonal" => Some(RuleNeighborhood::Hexagonal),
+            "oneDimensional" => Some(RuleNeighborhood::OneDimensional),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Lookup {
+    Table {
+        lines: Vec<TableLine>,
+        symmetries: Vec<Symmetries>,
+    },
+    Tree {
+        nodes: Vec<Vec<u32>>,
+    },
+}
+
+/// Neighbor orders a table line applies in, or any order at all for `permute`.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Symmetries {
+    permutations: Vec<Vec<usize>>,
+    permute: bool,
+}
+
+/// A transition of a `.table` file: the center, the neighbors in `RuleNeighborhood::offsets`
+/// order and the next state. Variables are bound while matching rather than expanded, since a
+/// line of many distinct variables stands for more transitions than fit in memory.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct TableLine {
+    cells: Vec<Cell>,
+    bindings: usize,
+    symmetries: usize,
+}
+
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Cell {
+    State(u32),
+    /// A variable, which takes the same value wherever its `binding` appears in the line.
+    Variable {
+        binding: usize,
+        values: Vec<u32>,
+    },
+}
+
+impl Cell {
+    fn bind(&self, state: u32, bindings: &mut [Option<u32>]) -> bool {
+        match self {
+            Cell::State(expected) => *expected == state,
+            Cell::Variable { binding, values } => match bindings[*binding] {
+                Some(bound) => bound == state,
+                None if values.contains(&state) => {
+                    bindings[*binding] = Some(state);
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+impl TableLine {
+    /// Next state if the line matches with its neighbors in the order of `permutation`.
+    fn next_state(&self, center: u32, neighbors: &[u32], permutation: &[usize]) -> Option<u32> {
+        let mut bindings = vec![None; self.bindings];
+
+        if !self.cells[0].bind(center, &mut bindings) {
+            return None;
+        }
+
+        for (i, &state) in neighbors.iter().enumerate() {
+            if !self.cells[1 + permutation[i]].bind(state, &mut bindings) {
+                return None;
+            }
+        }
+
+        self.output(&bindings)
+    }
+
+    /// Next state if the line matches with its neighbors in some order.
+    fn next_state_permuted(&self, center: u32, neighbors: &[u32]) -> Option<u32> {
+        let mut bindings = vec![None; self.bindings];
+        let mut used = vec![false; neighbors.len()];
+
+        if !self.cells[0].bind(center, &mut bindings)
+            || !self.assign(neighbors, &mut used, &mut bindings)
+        {
+            return None;
+        }
+
+        self.output(&bindings)
+    }
+
+    fn assign(&self, neighbors: &[u32], used: &mut [bool], bindings: &mut [Option<u32>]) -> bool {
+        let Some((&state, rest)) = neighbors.split_first() else {
+            return true;
+        };
+
+        for position in 0..used.len() {
+            if used[position] {
+                continue;
+            }
+
+            let saved = bindings.to_vec();
+
+            if self.cells[1 + position].bind(state, bindings) {
+                used[position] = true;
+
+                if self.assign(rest, used, bindings) {
+                    return true;
+                }
+
+                used[position] = false;
+            }
+
+            bindings.copy_from_slice(&saved);
+        }
+
+        false
+    }
+
+    fn output(&self, bindings: &[Option<u32>]) -> Option<u32> {
+        match self.cells.last()? {
+            Cell::State(state) => Some(*state),
+            Cell::Variable { binding, .. } => bindings[*binding],
+        }
+    }
+}
+
+/// Transition rule loaded from a Golly `.table` or `.tree` file.
+///
+/// Neighbor states are read from the ordered neighborhood linked by the grid constructors, so the
+/// space must be built with a neighborhood that contains every offset the rule looks at.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleTable {
+    states: u32,
+    neighborhood: RuleNeighborhood,
+    lookup: Lookup,
+    // lookup order mapped onto each ordered neighborhood the grid can produce, keyed by its size
+    positions: BTreeMap<usize, Vec<usize>>,
+}
+
+impl RuleTable {
+    fn new(
+        states: u32,
+        neighborhood: RuleNeighborhood,
+        order: Vec<(i32, i32)>,
+        lookup: Lookup,
+    ) -> Self {
+        let positions = [8, 6, 4, 2]
+            .into_iter()
+            .filter_map(|len| {
+                let layout = grid_neighborhood(len)?;
+                Some((len, neighborhood_positions(layout, &order)?))
+            })
+            .collect();
+
+        Self {
+            states,
+            neighborhood,
+            lookup,
+            positions,
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        let parse = match path.extension().and_then(|e| e.to_str()) {
+            Some("table") => Self::parse_table,
+            Some("tree") => Self::parse_tree,
+            _ => return Err(Error::RuleExtension(path.to_path_buf())),
+        };
+
+        parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn parse_table(source: &str) -> Result<Self, Error> {
+        let mut states = None;
+        let mut neighborhood = None;
+        let mut symmetries = String::from("none");
+        let mut variables = HashMap::<String, Vec<u32>>::new();
+        let mut lines = Vec::new();
+        let mut table_symmetries = Vec::<Symmetries>::new();
+
+        for (i, raw) in source.lines().enumerate() {
+            let line_number = i + 1;
+            let line = raw.split('#').next().unwrap().trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(value) = line.strip_prefix("n_states:") {
+                let value = value
+                    .trim()
+                    .parse::<u32>()
+                    .map_err(|_| parse_error(line_number, "invalid n_states"))?;
+
+                states = Some(value);
+            } else if let Some(value) = line.strip_prefix("neighborhood:") {
+                let value = value.trim();
+
+                neighborhood = Some(RuleNeighborhood::from_name(value).ok_or_else(|| {
+                    parse_error(line_number, format!("unknown neighborhood '{value}'"))
+                })?);
+            } else if let Some(value) = line.strip_prefix("symmetries:") {
+                symmetries = value.trim().to_string();
+            } else if let Some(definition) = line.strip_prefix("var ") {
+                let states = states.ok_or_else(|| parse_error(line_number, "missing n_states"))?;
+                let (name, values) = definition
+                    .split_once('=')
+                    .ok_or_else(|| parse_error(line_number, "expected 'var name={...}'"))?;
+                let values = values
+                    .trim()
+                    .strip_prefix('{')
+                    .and_then(|v| v.strip_suffix('}'))
+                    .ok_or_else(|| parse_error(line_number, "variable values must be in braces"))?;
+
+                let mut expanded = Vec::new();
+
+                for token in values.split(',').map(str::trim) {
+                    match variables.get(token) {
+                        Some(values) => expanded.extend_from_slice(values),
+                        None => expanded.push(parse_state(token, states, line_number)?),
+                    }
+                }
+
+                variables.insert(name.trim().to_string(), expanded);
+            } else {
+                let states = states.ok_or_else(|| parse_error(line_number, "missing n_states"))?;
+                let neighborhood =
+                    neighborhood.ok_or_else(|| parse_error(line_number, "missing neighborhood"))?;
+
+                let tokens = if line.contains(',') {
+                    line.split(',').map(|t| t.trim().to_string()).collect_vec()
+                } else {
+                    line.chars()
+                        .filter(|c| !c.is_whitespace())
+                        .map(String::from)
+                        .collect_vec()
+                };
+
+                let arity = neighborhood.offsets().len();
+
+                if tokens.len() != arity + 2 {
+                    return Err(parse_error(
+                        line_number,
+                        format!("expected {} entries, found {}", arity + 2, tokens.len()),
+                    ));
+                }
+
+                let permutations =
+                    symmetry_permutations(&symmetries, neighborhood).ok_or_else(|| {
+                        parse_error(line_number, format!("unknown symmetries '{symmetries}'"))
+                    })?;
+                let current = Symmetries {
+                    permutations,
+                    permute: symmetries == "permute",
+                };
+
+                if table_symmetries.last() != Some(&current) {
+                    table_symmetries.push(current);
+                }
+
+                let names = tokens
+                    .iter()
+                    .filter(|t| variables.contains_key(*t))
+                    .unique()
+                    .collect_vec();
+
+                let cells = tokens
+                    .iter()
+                    .map(|token| match names.iter().position(|name| *name == token) {
+                        Some(binding) => Ok(Cell::Variable {
+                            binding,
+                            values: variables[token].clone(),
+                        }),
+                        None => parse_state(token, states, line_number).map(Cell::State),
+                    })
+                    .collect::<Result<Vec<Cell>, Error>>()?;
+
+                if !tokens[..=arity].contains(&tokens[arity + 1])
+                    && names.contains(&&tokens[arity + 1])
+                {
+                    return Err(parse_error(
+                        line_number,
+                        format!(
+                            "output variable '{}' is not bound by the inputs",
+                            tokens[arity + 1]
+                        ),
+                    ));
+                }
+
+                lines.push(TableLine {
+                    cells,
+                    bindings: names.len(),
+                    symmetries: table_symmetries.len() - 1,
+                });
+            }
+        }
+
+        // problems found once the whole file is read are reported on its last line
+        let end = source.lines().count().max(1);
+        let states = states.ok_or_else(|| parse_error(end, "missing n_states"))?;
+        let neighborhood = neighborhood.ok_or_else(|| parse_error(end, "missing neighborhood"))?;
+
+        Ok(Self::new(
+            states,
+            neighborhood,
+            neighborhood.offsets().to_vec(),
+            Lookup::Table {
+                lines,
+                symmetries: table_symmetries,
+            },
+        ))
+    }
+
+    pub fn parse_tree(source: &str) -> Result<Self, Error> {
+        let mut states = None;
+        let mut neighbors = None;
+        let mut expected_nodes = None;
+        let mut nodes = Vec::<Vec<u32>>::new();
+        let mut levels = Vec::<u32>::new();
+
+        for (i, raw) in source.lines().enumerate() {
+            let line_number = i + 1;
+            let line = raw.split('#').next().unwrap().trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value
+                    .trim()
+                    .parse::<u32>()
+                    .map_err(|_| parse_error(line_number, format!("invalid value for {key}")))?;
+
+                match key.trim() {
+                    "num_states" => states = Some(value),
+                    "num_neighbors" => neighbors = Some(value),
+                    "num_nodes" => expected_nodes = Some(value as usize),
+                    other => {
+                        return Err(parse_error(line_number, format!("unknown key '{other}'")))
+                    }
+                }
+
+                continue;
+            }
+
+            let states = states.ok_or_else(|| parse_error(line_number, "missing num_states"))?;
+            let values = line
+                .split_whitespace()
+                .map(|v| v.parse::<u32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| parse_error(line_number, "expected a tree node"))?;
+
+            let (&level, children) = values
+                .split_first()
+                .ok_or_else(|| parse_error(line_number, "empty tree node"))?;
+
+            if children.len() != states as usize {
+                return Err(parse_error(
+                    line_number,
+                    format!("expected {states} children, found {}", children.len()),
+                ));
+            }
+
+            for &child in children {
+                let valid = if level == 1 {
+                    child < states
+                } else {
+                    levels.get(child as usize) == Some(&(level - 1))
+                };
+
+                if !valid {
+                    return Err(parse_error(line_number, format!("invalid child {child}")));
+                }
+            }
+
+            nodes.push(children.to_vec());
+            levels.push(level);
+        }
+
+        let end = source.lines().count().max(1);
+        let states = states.ok_or_else(|| parse_error(end, "missing num_states"))?;
+        let neighborhood = match neighbors {
+            Some(8) => RuleNeighborhood::Moore,
+            Some(4) => RuleNeighborhood::VonNeumann,
+            _ => return Err(parse_error(end, "num_neighbors must be 4 or 8")),
+        };
+        let order = tree_order(neighborhood);
+
+        if expected_nodes.is_some_and(|n| n != nodes.len()) {
+            return Err(parse_error(end, "num_nodes does not match the node count"));
+        }
+
+        if levels.last() != Some(&(order.len() as u32 + 1)) {
+            return Err(parse_error(
+                end,
+                "the last node must be the root of the tree",
+            ));
+        }
+
+        Ok(Self::new(
+            states,
+            neighborhood,
+            order,
+            Lookup::Tree { nodes },
+        ))
+    }
+
+    pub fn neighborhood(&self) -> RuleNeighborhood {
+        self.neighborhood
+    }
+
+    /// Neighbor offsets in the order `next_state` reads them.
+    fn lookup_order(&self) -> Vec<(i32, i32)> {
+        match self.lookup {
+            Lookup::Table { .. } => self.neighborhood.offsets().to_vec(),
+            Lookup::Tree { .. } => tree_order(self.neighborhood),
+        }
+    }
+
+    /// Next state of `center` among `neighbors`, read in lookup order. The first table line
+    /// that matches applies; neighborhoods without one, and states outside the rule, keep the
+    /// center as is.
+    pub fn next_state(&self, center: u32, neighbors: &[u32]) -> u32 {
+        match &self.lookup {
+            Lookup::Table { lines, symmetries } => lines
+                .iter()
+                .find_map(|line| {
+                    let symmetries = &symmetries[line.symmetries];
+
+                    if symmetries.permute {
+                        return line.next_state_permuted(center, neighbors);
+                    }
+
+                    symmetries
+                        .permutations
+                        .iter()
+                        .find_map(|permutation| line.next_state(center, neighbors, permutation))
+                })
+                .unwrap_or(center),
+            Lookup::Tree { nodes } => {
+                let mut current = nodes.len() - 1;
+
+                for &state in neighbors {
+                    match nodes[current].get(state as usize) {
+                        Some(&child) => current = child as usize,
+                        None => return center,
+                    }
+                }
+
+                nodes[current]
+                    .get(center as usize)
+                    .copied()
+                    .unwrap_or(center)
+            }
+        }
+    }
+}
+
+/// Order in which Golly's rule trees descend through the neighbors.
+fn tree_order(neighborhood: RuleNeighborhood) -> Vec<(i32, i32)> {
+    match neighborhood {
+        RuleNeighborhood::Moore => vec![
+            (-1, -1),
+            (1, -1),
+            (-1, 1),
+            (1, 1),
+            (0, -1),
+            (-1, 0),
+            (1, 0),
+            (0, 1),
+        ],
+        _ => vec![(0, -1), (-1, 0), (1, 0), (0, 1)],
+    }
+}
+
+fn parse_state(token: &str, states: u32, line: usize) -> Result<u32, Error> {
+    match token.parse::<u32>() {
+        Ok(state) if state < states => Ok(state),
+        Ok(state) => Err(parse_error(line, format!("state {state} out of range"))),
+        Err(_) => Err(parse_error(line, format!("unknown variable '{token}'"))),
+    }
+}
+
+fn symmetry_permutations(name: &str, neighborhood: RuleNeighborhood) -> Option<Vec<Vec<usize>>> {
+    let len = neighborhood.offsets().len();
+    let rotation = |r: usize| (0..len).map(|i| (i + r) % len).collect_vec();
+
+    if neighborhood == RuleNeighborhood::OneDimensional {
+        return match name {
+            "none" | "permute" => Some(vec![vec![0, 1]]),
+            "reflect" => Some(vec![vec![0, 1], vec![1, 0]]),
+            _ => None,
+        };
+    }
+
+    let (rotations, reflect) = match name {
+        "none" | "permute" => (1, false),
+        "reflect_horizontal" | "reflect" => (1, true),
+        _ => {
+            let rest = name.strip_prefix("rotate")?;
+            let (count, reflect) = match rest.strip_suffix("reflect") {
+                Some(count) => (count, true),
+                None => (rest, false),
+            };
+
+            (count.parse::<usize>().ok()?, reflect)
+        }
+    };
+
+    if len.checked_rem(rotations) != Some(0) {
+        return None;
+    }
+
+    let mut permutations = (0..rotations)
+        .map(|r| rotation(r * len / rotations))
+        .collect_vec();
+
+    if reflect {
+        let reflected = permutations
+            .iter()
+            .map(|p| (0..len).map(|i| p[(len - i) % len]).collect_vec())
+            .collect_vec();
+
+        permutations.extend(reflected);
+    }
+
+    Some(permutations)
+}
+
+impl<N, E> LocalDynamic<N, E> for RuleTable
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        self.states
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        self.positions.contains_key(&neighbors)
+    }
+
+    fn reads_grid_layout(&self) -> bool {
+        true
+    }
+
+    fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
+        let positions = self
+            .positions
+            .get(&nodes.len())
+            .expect("the grid neighborhood does not cover the rule's neighborhood");
+
+        let neighbors = positions.iter().map(|&p| nodes[p].state()).collect_vec();
+
+        N::from_state(self.next_state(node.state(), &neighbors))
+    }
+}
+
+impl TransitionRule for RuleTable {
+    fn alphabet(&self) -> u32 {
+        self.states
+    }
+
+    fn neighborhood_size(&self) -> Result<usize, Error> {
+        Ok(self.neighborhood.offsets().len() + 1)
+    }
+
+    /// Center followed by the neighbors in the order of `RuleNeighborhood::offsets`.
+    fn transition(&self, cells: &[u32]) -> u32 {
+        let offsets = self.neighborhood.offsets();
+        let neighbors = self
+            .lookup_order()
+            .iter()
+            .map(|offset| cells[1 + offsets.iter().position(|o| o == offset).unwrap()])
+            .collect_vec();
+
+        self.next_state(cells[0], &neighbors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::Error,
+        spaces::implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::{
+                one_dimensional_neighborhood, ONE_DIMENSIONAL_NEIGHBORHOOD,
+                VON_NEUMANN_NEIGHBORHOOD,
+            },
+        },
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    fn table(neighborhood: &str, symmetries: &str, lines: &str) -> RuleTable {
+        let source =
+            format!("n_states:2\nneighborhood:{neighborhood}\nsymmetries:{symmetries}\n{lines}\n");
+
+        RuleTable::parse_table(&source).unwrap()
+    }
+
+    /// Next state of a 0 center whose neighbors at `ones`, in `offsets` order, are 1.
+    fn fires(rule: &RuleTable, ones: &[usize]) -> bool {
+        let mut cells = vec![0; rule.neighborhood().offsets().len() + 1];
+
+        for &i in ones {
+            cells[1 + i] = 1;
+        }
+
+        rule.transition(&cells) == 1
+    }
+
+    #[test]
+    fn moore_symmetries() {
+        // N, NE, E, SE, S, SW, W, NW
+        let none = table("Moore", "none", "0,1,0,0,0,0,0,0,0,1");
+        assert!(fires(&none, &[0]));
+        assert!(!fires(&none, &[2]));
+
+        let rotate4 = table("Moore", "rotate4", "0,1,0,0,0,0,0,0,0,1");
+        assert!(fires(&rotate4, &[2]) && fires(&rotate4, &[6]));
+        assert!(!fires(&rotate4, &[1]));
+
+        let rotate8 = table("Moore", "rotate8", "0,1,0,0,0,0,0,0,0,1");
+        assert!((0..8).all(|i| fires(&rotate8, &[i])));
+
+        // N and NE mirror onto N and NW, which no rotation by quarter turns reaches
+        let rotate4 = table("Moore", "rotate4", "0,1,1,0,0,0,0,0,0,1");
+        let rotate4reflect = table("Moore", "rotate4reflect", "0,1,1,0,0,0,0,0,0,1");
+        assert!(fires(&rotate4, &[2, 3]) && !fires(&rotate4, &[0, 7]));
+        assert!(fires(&rotate4reflect, &[2, 3]) && fires(&rotate4reflect, &[0, 7]));
+
+        let reflect = table("Moore", "reflect_horizontal", "0,1,1,0,0,0,0,0,0,1");
+        assert!(fires(&reflect, &[0, 7]) && !fires(&reflect, &[2, 3]));
+
+        let permute = table("Moore", "permute", "0,1,1,0,0,0,0,0,0,1");
+        assert!(fires(&permute, &[3, 7]));
+        assert!(!fires(&permute, &[3]) && !fires(&permute, &[1, 3, 7]));
+    }
+
+    #[test]
+    fn von_neumann_variables_bind_once_per_line() {
+        let rule = table(
+            "vonNeumann",
+            "none",
+            "var a={0,1}\nvar b={0,1}\n0,a,a,b,0,1",
+        );
+
+        // N, E, S, W
+        assert!(fires(&rule, &[]) && fires(&rule, &[0, 1]) && fires(&rule, &[0, 1, 2]));
+        assert!(!fires(&rule, &[0]) && !fires(&rule, &[3]));
+
+        let rotate4 = table("vonNeumann", "rotate4", "0,1,0,0,0,1");
+        assert!((0..4).all(|i| fires(&rotate4, &[i])));
+        assert!(!fires(&rotate4, &[0, 1]));
+    }
+
+    #[test]
+    fn hexagonal_and_one_dimensional_symmetries() {
+        let rotate6 = table("hexagonal", "rotate6", "0,1,0,0,0,0,0,1");
+        assert!((0..6).all(|i| fires(&rotate6, &[i])));
+
+        let rotate2 = table("hexagonal", "rotate2", "0,1,0,0,0,0,0,1");
+        assert!(fires(&rotate2, &[3]) && !fires(&rotate2, &[1]));
+
+        let rotate3 = table("hexagonal", "rotate3", "0,1,0,0,0,0,0,1");
+        assert!(fires(&rotate3, &[2]) && fires(&rotate3, &[4]) && !fires(&rotate3, &[1]));
+
+        let rotate6reflect = table("hexagonal", "rotate6reflect", "0,1,1,0,0,0,0,1");
+        assert!(fires(&rotate6reflect, &[0, 5]) && !fires(&rotate6reflect, &[0, 2]));
+
+        let none = table("oneDimensional", "none", "0,1,0,1");
+        assert!(fires(&none, &[0]) && !fires(&none, &[1]));
+
+        let reflect = table("oneDimensional", "reflect", "0,1,0,1");
+        assert!(fires(&reflect, &[0]) && fires(&reflect, &[1]));
+    }
+
+    #[test]
+    fn tables_read_grid_neighbors_by_offset() {
+        let rule = table("Moore", "rotate4", "0,1,0,0,0,0,0,0,0,1");
+
+        let mut nodes = vec![DiscreteState::from_state(0); 25];
+        nodes[7] = DiscreteState::from_state(1);
+
+        let space = HyperGraphHeap::new_grid(&nodes, 5, 5, ());
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(rule));
+
+        system.compute_sync();
+
+        let lit = system
+            .space_state()
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.state() == 1)
+            .map(|(i, _)| i)
+            .collect_vec();
+
+        // the cell itself and its orthogonal neighbors, which see it N, E, S or W of them
+        assert_eq!(lit, vec![2, 6, 7, 8, 12]);
+    }
+
+    #[test]
+    fn trees_descend_in_golly_order() {
+        // the next state is the center flipped by the west neighbor
+        let source = "num_states=2\nnum_neighbors=4\nnum_nodes=8\n\
+                      1 0 1\n1 1 0\n2 0 0\n2 1 1\n3 2 2\n3 3 3\n4 4 5\n5 6 6\n";
+        let rule = RuleTable::parse_tree(source).unwrap();
+
+        assert_eq!(rule.neighborhood(), RuleNeighborhood::VonNeumann);
+        // center, N, E, S, W
+        assert_eq!(rule.transition(&[0, 0, 0, 0, 1]), 1);
+        assert_eq!(rule.transition(&[1, 0, 0, 0, 1]), 0);
+        assert_eq!(rule.transition(&[0, 1, 1, 1, 0]), 0);
+
+        let mut nodes = vec![DiscreteState::from_state(0); 9];
+        nodes[3] = DiscreteState::from_state(1);
+
+        let space =
+            HyperGraphHeap::new_grid_with_neighborhood(&nodes, 3, 3, &VON_NEUMANN_NEIGHBORHOOD, ());
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(rule));
+
+        system.compute_sync();
+
+        // only the cell east of the lit one has it to its west
+        let states = system.space_state().iter().map(|n| n.state()).collect_vec();
+        assert_eq!(states, vec![0, 0, 0, 1, 1, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn malformed_rules_report_their_line() {
+        let error = |source: &str| match RuleTable::parse_table(source) {
+            Err(Error::RuleParse { line, .. }) => line,
+            other => panic!("expected a parse error, got {other:?}"),
+        };
+
+        let header = "n_states:2\nneighborhood:vonNeumann\n";
+
+        assert_eq!(error(&format!("{header}0,1,0,0,1")), 3);
+        assert_eq!(error(&format!("{header}0,1,0,0,0,2")), 3);
+        assert_eq!(
+            error(&format!("{header}symmetries:rotate3\n0,1,0,0,0,1")),
+            4
+        );
+        assert_eq!(error(&format!("{header}\n0,x,0,0,0,1")), 4);
+        assert_eq!(error("neighborhood:hexagon\n"), 1);
+        assert_eq!(error("n_states:2\n\nvar a={0,1}\n"), 3);
+        assert_eq!(error(&format!("{header}var a={{0,1}}\n0,0,0,0,0,a")), 4);
+
+        let tree = RuleTable::parse_tree("num_states=2\nnum_neighbors=4\n1 0 1\n2 0 5\n");
+        assert!(matches!(tree, Err(Error::RuleParse { line: 4, .. })));
+
+        assert!(matches!(
+            RuleTable::from_file("rule.txt"),
+            Err(Error::RuleExtension(_))
+        ));
+    }
+
+    #[test]
+    fn lines_of_many_variables_bind_lazily() {
+        // 16^8 combinations of the neighbors, far more than could be expanded
+        let mut source = String::from("n_states:16\nneighborhood:Moore\nsymmetries:rotate8\n");
+        let names = ["a", "b", "c", "d", "e", "f", "g", "h"];
+
+        for name in names {
+            source.push_str(&format!(
+                "var {name}={{0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15}}\n"
+            ));
+        }
+        source.push_str("var x={0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15}\n");
+        // a center of 1 copies its north neighbor, whatever the others are
+        source.push_str(&format!("1,x,{},x\n", names[1..].join(",")));
+
+        let rule = RuleTable::parse_table(&source).unwrap();
+
+        assert_eq!(rule.transition(&[1, 9, 3, 4, 5, 6, 7, 8, 2]), 9);
+        // no line covers other centers
+        assert_eq!(rule.transition(&[0, 9, 3, 4, 5, 6, 7, 8, 2]), 0);
+    }
+
+    #[test]
+    fn permuted_lines_match_any_order() {
+        let rule = table("Moore", "permute", "var a={0,1}\n0,a,a,1,0,0,0,0,0,1");
+
+        // one neighbor alive, or three with two of them bound to the same variable
+        assert!(fires(&rule, &[5]));
+        assert!(fires(&rule, &[1, 4, 6]));
+        assert!(!fires(&rule, &[1, 4]));
+        assert!(!fires(&rule, &[]));
+    }
+
+    #[test]
+    fn states_outside_the_rule_keep_the_center() {
+        let source = "num_states=2\nnum_neighbors=4\nnum_nodes=8\n\
+                      1 0 1\n1 1 0\n2 0 0\n2 1 1\n3 2 2\n3 3 3\n4 4 5\n5 6 6\n";
+        let rule = RuleTable::parse_tree(source).unwrap();
+
+        assert_eq!(rule.next_state(0, &[0, 7, 0, 0]), 0);
+        assert_eq!(rule.next_state(5, &[0, 0, 0, 0]), 5);
+    }
+
+    #[test]
+    fn uncovered_neighborhoods_are_refused() {
+        let rule = table("vonNeumann", "none", "0,1,0,0,0,1");
+        let nodes = vec![DiscreteState::from_state(0); 6];
+        let space = HyperGraphHeap::new_grid_with_neighborhood(
+            &nodes,
+            6,
+            1,
+            &ONE_DIMENSIONAL_NEIGHBORHOOD,
+            (),
+        );
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(rule));
+
+        assert!(matches!(
+            system.try_compute_sync(),
+            Err(Error::Neighborhood { neighbors: 2, .. })
+        ));
+
+        // as many neighbors as the von Neumann grid, but a ring of radius 2
+        let rule = table("vonNeumann", "none", "0,1,0,0,0,1");
+        let space = HyperGraphHeap::new_grid_with_neighborhood(
+            &nodes,
+            6,
+            1,
+            &one_dimensional_neighborhood(2),
+            (),
+        );
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(rule));
+
+        assert!(matches!(
+            system.try_compute_sync(),
+            Err(Error::Neighborhood { neighbors: 4, .. })
+        ));
+    }
+}
@@ -0,0 +1,531 @@
+use std::{collections::BTreeMap, fmt::Debug, hash::Hash, io::Write};
+
+use crate::{
+    dynamics::{context::NodeRng, local::LocalDynamic},
+    error::Error,
+    spaces::local::{HyperEdge, LocalHyperGraph, Stateable},
+    system::{backend::BackendKind, dynamical_system::DynamicalSystem},
+};
+
+/// Bak–Tang–Wiesenfeld sandpile: node states count grains, and a node holding at least
+/// `threshold` grains topples, giving one grain to each neighbor.
+///
+/// The threshold defaults to the node's degree. A node only sees its neighbors' grains, so `update`
+/// assumes they share its threshold and link back to it, which holds on the grids. On other
+/// spaces relax it with `SandpileDriver`, which topples along the links themselves.
+#[derive(Clone, Hash, Eq, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sandpile {
+    threshold: Option<u32>,
+}
+
+impl Sandpile {
+    pub fn new() -> Self {
+        Self { threshold: None }
+    }
+
+    pub fn with_threshold(threshold: u32) -> Self {
+        Self {
+            threshold: Some(threshold),
+        }
+    }
+
+    pub fn threshold(&self, degree: usize) -> u32 {
+        self.threshold.unwrap_or(degree as u32)
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for Sandpile
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    /// Stable grain counts for a fixed threshold; unbounded otherwise.
+    fn states(&self) -> u32 {
+        self.threshold.unwrap_or(u32::MAX)
+    }
+
+    fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
+        let threshold = self.threshold(nodes.len());
+        let grains = node.state();
+
+        let incoming = nodes.iter().filter(|n| n.state() >= threshold).count() as u32;
+        let outgoing = if grains >= threshold { threshold } else { 0 };
+
+        N::from_state(grains - outgoing + incoming)
+    }
+}
+
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug, Default)]
+pub struct Avalanche {
+    /// Number of topplings.
+    pub size: usize,
+    /// Number of parallel relaxation steps.
+    pub duration: usize,
+    /// Number of distinct nodes that toppled.
+    pub area: usize,
+}
+
+/// Drops grains on a sandpile system, relaxes it back to stability after each one and records the
+/// resulting avalanches. Sink nodes never topple and lose every grain they receive, which gives
+/// open boundaries when placed along the border of a grid.
+///
+/// A toppling node loses its threshold and gives one grain to each node in its neighbor list, so
+/// the driver works on any space, directed or irregular.
+#[derive(Clone, Debug)]
+pub struct SandpileDriver<S, N, E>
+where
+    S: LocalHyperGraph<N, E> + Debug + Clone,
+    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
+{
+    system: DynamicalSystem<S, Sandpile, N, E>,
+    sinks: Vec<bool>,
+    thresholds: Vec<u32>,
+    neighbors: Vec<Vec<usize>>,
+    lost: u64,
+    rng: NodeRng,
+    avalanches: Vec<Avalanche>,
+}
+
+impl<S, N, E> SandpileDriver<S, N, E>
+where
+    S: LocalHyperGraph<N, E> + Debug + Clone,
+    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
+{
+    /// Empties `sinks` and checks every other node relaxes: its threshold covers the grains it
+    /// gives away, and toppling from it eventually reaches a sink or a node with a threshold above
+    /// its degree, which loses the difference.
+    pub fn new(
+        mut system: DynamicalSystem<S, Sandpile, N, E>,
+        sinks: &[usize],
+    ) -> Result<Self, Error> {
+        let len = system.space().nodes().len();
+
+        let mut sink_mask = vec![false; len];
+        sinks.iter().for_each(|&i| sink_mask[i] = true);
+
+        let neighbors = (0..len)
+            .map(|i| {
+                system
+                    .space()
+                    .node_neighbors()
+                    .get(&i)
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<Vec<usize>>>();
+
+        let thresholds = neighbors
+            .iter()
+            .map(|links| system.dynamic().threshold(links.len()))
+            .collect::<Vec<u32>>();
+
+        for i in (0..len).filter(|&i| !sink_mask[i]) {
+            let degree = neighbors[i].len();
+
+            if thresholds[i] == 0 || (thresholds[i] as usize) < degree {
+                return Err(Error::Threshold {
+                    node: i,
+                    threshold: thresholds[i],
+                    degree,
+                });
+            }
+        }
+
+        // walk the links backwards from the nodes that lose grains
+        let mut feeders = vec![Vec::new(); len];
+
+        for (i, links) in neighbors.iter().enumerate() {
+            links.iter().for_each(|&j| feeders[j].push(i));
+        }
+
+        let mut drains = (0..len)
+            .map(|i| sink_mask[i] || thresholds[i] as usize > neighbors[i].len())
+            .collect::<Vec<bool>>();
+        let mut frontier = (0..len).filter(|&i| drains[i]).collect::<Vec<usize>>();
+
+        while let Some(j) = frontier.pop() {
+            for &i in &feeders[j] {
+                if !drains[i] {
+                    drains[i] = true;
+                    frontier.push(i);
+                }
+            }
+        }
+
+        if let Some(node) = (0..len).find(|&i| !drains[i]) {
+            return Err(Error::NoSink { node });
+        }
+
+        let rng = NodeRng::new(system.seed());
+        let mut lost = 0;
+
+        system.update_space(|nodes| {
+            for &i in sinks {
+                lost += nodes[i].state() as u64;
+                nodes[i] = N::from_state(0);
+            }
+        });
+
+        Ok(Self {
+            system,
+            sinks: sink_mask,
+            thresholds,
+            neighbors,
+            lost,
+            rng,
+            avalanches: Vec::new(),
+        })
+    }
+
+    pub fn system(&self) -> &DynamicalSystem<S, Sandpile, N, E> {
+        &self.system
+    }
+
+    pub fn avalanches(&self) -> &[Avalanche] {
+        &self.avalanches
+    }
+
+    /// Grains that left the pile, through sinks or toppling thresholds above the degree. With the
+    /// grains on the nodes they add up to every grain ever placed.
+    pub fn lost_grains(&self) -> u64 {
+        self.lost
+    }
+
+    /// Adds one grain to `node` and relaxes. Avalanches with at least one toppling are recorded.
+    /// A grain dropped on a sink is absorbed right away and counted as lost.
+    pub fn add_grain(&mut self, node: usize) -> Avalanche {
+        if self.sinks[node] {
+            self.lost += 1;
+
+            return Avalanche::default();
+        }
+
+        self.system.update_space(|nodes| {
+            let grains = nodes[node].state();
+            nodes[node].set_state(grains + 1);
+        });
+
+        let avalanche = self.relax();
+
+        if avalanche.size > 0 {
+            self.avalanches.push(avalanche);
+        }
+
+        avalanche
+    }
+
+    /// Adds `grains` grains one at a time on uniformly chosen non-sink nodes. Piles made of sinks
+    /// only have nowhere to put them and are left as they are.
+    pub fn drive(&mut self, grains: usize) {
+        let candidates = (0..self.sinks.len())
+            .filter(|&i| !self.sinks[i])
+            .collect::<Vec<usize>>();
+
+        if candidates.is_empty() {
+            return;
+        }
+
+        for _ in 0..grains {
+            let node = candidates[self.rng.gen_range(candidates.len() as u32) as usize];
+            self.add_grain(node);
+        }
+    }
+
+    /// Topples every unstable node at once, one generation of the system per round, until none
+    /// is left.
+    pub fn relax(&mut self) -> Avalanche {
+        let mut avalanche = Avalanche::default();
+        let mut toppled = vec![false; self.sinks.len()];
+
+        loop {
+            let mut grains = self
+                .system
+                .space()
+                .nodes()
+                .iter()
+                .map(|n| n.state())
+                .collect::<Vec<u32>>();
+
+            let unstable = (0..grains.len())
+                .filter(|&i| !self.sinks[i] && grains[i] >= self.thresholds[i])
+                .collect::<Vec<usize>>();
+
+            if unstable.is_empty() {
+                break;
+            }
+
+            avalanche.size += unstable.len();
+            avalanche.duration += 1;
+
+            for &i in &unstable {
+                toppled[i] = true;
+                grains[i] -= self.thresholds[i];
+                self.lost += (self.thresholds[i] as usize - self.neighbors[i].len()) as u64;
+
+                for &j in &self.neighbors[i] {
+                    if self.sinks[j] {
+                        self.lost += 1;
+                    } else {
+                        grains[j] += 1;
+                    }
+                }
+            }
+
+            let nodes = self
+                .system
+                .space()
+                .nodes()
+                .iter()
+                .zip(grains)
+                .map(|(node, grains)| {
+                    let mut node = node.clone();
+                    node.set_state(grains);
+                    node
+                })
+                .collect();
+
+            self.system.commit(nodes, BackendKind::CpuSerial);
+        }
+
+        avalanche.area = toppled.iter().filter(|&&t| t).count();
+
+        avalanche
+    }
+
+    /// Number of recorded avalanches of each size.
+    pub fn size_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+
+        for avalanche in &self.avalanches {
+            *histogram.entry(avalanche.size).or_insert(0) += 1;
+        }
+
+        histogram
+    }
+
+    pub fn write_size_histogram_csv(&self, mut writer: impl Write) -> std::io::Result<()> {
+        writeln!(writer, "size,count")?;
+
+        for (size, count) in self.size_histogram() {
+            writeln!(writer, "{size},{count}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::spaces::implementations::{
+        basic::{DiscreteState, HyperGraphHeap},
+        grid::VON_NEUMANN_NEIGHBORHOOD,
+    };
+
+    type Grid = HyperGraphHeap<DiscreteState, (), (u32, u32)>;
+
+    /// Von Neumann grid with sinks all around its border.
+    fn open_grid(
+        side: u32,
+        grains: u32,
+        sandpile: Sandpile,
+    ) -> Result<SandpileDriver<Grid, DiscreteState, ()>, Error> {
+        let nodes = vec![DiscreteState::from_state(grains); (side * side) as usize];
+        let space = HyperGraphHeap::new_grid_with_neighborhood(
+            &nodes,
+            side,
+            side,
+            &VON_NEUMANN_NEIGHBORHOOD,
+            (),
+        );
+
+        let sinks = (0..side * side)
+            .filter(|i| {
+                let (x, y) = (i % side, i / side);
+                x == 0 || y == 0 || x == side - 1 || y == side - 1
+            })
+            .map(|i| i as usize)
+            .collect::<Vec<usize>>();
+
+        SandpileDriver::new(
+            DynamicalSystem::new(Box::new(space), Box::new(sandpile)),
+            &sinks,
+        )
+    }
+
+    fn grains<S, N, E>(driver: &SandpileDriver<S, N, E>) -> u64
+    where
+        S: LocalHyperGraph<N, E> + Debug + Clone,
+        N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
+        E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
+    {
+        driver
+            .system()
+            .space_state()
+            .iter()
+            .map(|n| n.state() as u64)
+            .sum()
+    }
+
+    #[test]
+    fn full_grid_relaxes_as_worked_out_by_hand() {
+        let mut driver = open_grid(5, 3, Sandpile::new()).unwrap();
+
+        let avalanche = driver.add_grain(12);
+
+        // the center, then the four edge midpoints, then the center again with the four corners
+        assert_eq!(
+            avalanche,
+            Avalanche {
+                size: 10,
+                duration: 3,
+                area: 9,
+            }
+        );
+        assert_eq!(driver.system().generation(), 3);
+
+        let interior =
+            [6, 7, 8, 11, 12, 13, 16, 17, 18].map(|i| driver.system().space_state()[i].state());
+        assert_eq!(interior, [1, 3, 1, 3, 0, 3, 1, 3, 1]);
+
+        // the border started with grains too, which the sinks dropped
+        assert_eq!(grains(&driver) + driver.lost_grains(), 25 * 3 + 1);
+    }
+
+    #[test]
+    fn grains_are_conserved_on_directed_irregular_graphs() {
+        // 5 only receives; 2 and 0 feed each other, so only the chain through 4 drains them
+        let links: [&[usize]; 6] = [&[1, 2, 3], &[2], &[0, 4], &[4], &[5], &[]];
+        let edges = links
+            .iter()
+            .enumerate()
+            .map(|(i, links)| (i, vec![(links.to_vec(), ())]))
+            .collect::<HashMap<usize, HyperEdge<()>>>();
+
+        let space = HyperGraphHeap::from_nodes_and_edges(
+            vec![DiscreteState::from_state(0); links.len()],
+            edges,
+            &(),
+        );
+        let system = DynamicalSystem::new(Box::new(space), Box::new(Sandpile::new()));
+        let mut driver = SandpileDriver::new(system, &[5]).unwrap();
+
+        for grain in 1..=500 {
+            driver.add_grain(grain % 5);
+
+            assert_eq!(grains(&driver) + driver.lost_grains(), grain as u64);
+        }
+
+        let states = driver.system().space_state();
+        assert!((0..5).all(|i| states[i].state() < links[i].len() as u32));
+        assert!(driver.lost_grains() > 0);
+    }
+
+    #[test]
+    fn thresholds_above_the_degree_dissipate() {
+        let mut driver = open_grid(6, 0, Sandpile::with_threshold(6)).unwrap();
+
+        driver.drive(2000);
+
+        assert_eq!(grains(&driver) + driver.lost_grains(), 2000);
+        assert!(driver.system().space_state().iter().all(|n| n.state() < 6));
+    }
+
+    #[test]
+    fn piles_that_never_settle_are_refused() {
+        let nodes = vec![DiscreteState::from_state(0); 16];
+
+        // a torus without sinks keeps every grain it is given
+        let closed =
+            HyperGraphHeap::new_grid_with_neighborhood(&nodes, 4, 4, &VON_NEUMANN_NEIGHBORHOOD, ());
+        let system = DynamicalSystem::new(Box::new(closed), Box::new(Sandpile::new()));
+        assert!(matches!(
+            SandpileDriver::new(system, &[]),
+            Err(Error::NoSink { .. })
+        ));
+
+        assert!(matches!(
+            open_grid(5, 0, Sandpile::with_threshold(0)),
+            Err(Error::Threshold { threshold: 0, .. })
+        ));
+
+        // a lone node topples at 0 grains
+        let isolated = HyperGraphHeap::from_nodes_and_edges(
+            vec![DiscreteState::from_state(0); 2],
+            HashMap::from([(0, vec![(vec![1], ())])]),
+            &(),
+        );
+        let system = DynamicalSystem::new(Box::new(isolated), Box::new(Sandpile::new()));
+        assert!(matches!(
+            SandpileDriver::new(system, &[0]),
+            Err(Error::Threshold {
+                node: 1,
+                degree: 0,
+                ..
+            })
+        ));
+
+        // toppling at 3 while giving to 4 neighbors would make grains
+        assert!(matches!(
+            open_grid(5, 0, Sandpile::with_threshold(3)),
+            Err(Error::Threshold {
+                threshold: 3,
+                degree: 4,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn avalanche_statistics_are_consistent() {
+        let mut driver = open_grid(20, 0, Sandpile::new()).unwrap();
+
+        driver.drive(5000);
+
+        let avalanches = driver.avalanches();
+        assert!(!avalanches.is_empty());
+
+        for avalanche in avalanches {
+            assert!(avalanche.size >= 1);
+            assert!(avalanche.area <= avalanche.size && avalanche.duration <= avalanche.size);
+            assert!(avalanche.area <= 18 * 18);
+        }
+
+        // once critical, avalanches span orders of magnitude
+        let histogram = driver.size_histogram();
+        assert_eq!(histogram.values().sum::<usize>(), avalanches.len());
+        assert_eq!(histogram.keys().next(), Some(&1));
+        assert!(*histogram.keys().last().unwrap() > 100);
+
+        let mut csv = Vec::new();
+        driver.write_size_histogram_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        assert!(csv.starts_with("size,count\n1,"));
+        assert_eq!(csv.lines().count(), histogram.len() + 1);
+
+        assert!(driver.system().space_state().iter().all(|n| n.state() < 4));
+        assert_eq!(grains(&driver) + driver.lost_grains(), 5000);
+    }
+
+    #[test]
+    fn sinks_absorb_grains_dropped_on_them() {
+        let mut driver = open_grid(5, 1, Sandpile::new()).unwrap();
+
+        assert_eq!(driver.add_grain(0), Avalanche::default());
+        assert_eq!(driver.system().space_state()[0].state(), 0);
+        assert_eq!(driver.lost_grains(), 16 + 1);
+
+        // a 2 by 2 grid is all border, so every node is a sink
+        let mut driver = open_grid(2, 1, Sandpile::new()).unwrap();
+        driver.drive(10);
+
+        assert_eq!(grains(&driver), 0);
+        assert!(driver.avalanches().is_empty());
+    }
+}
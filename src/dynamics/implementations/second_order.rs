@@ -0,0 +1,188 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::{context::UpdateContext, local::LocalDynamic},
+    spaces::local::{HyperEdge, Stateable},
+};
+
+/// Fredkin's construction: the next state is the wrapped rule's output minus the state one
+/// generation back, modulo the number of states (an XOR for two states). Whatever the wrapped
+/// rule, the pair of generations can be run backward with `compute_sync_backward`.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecondOrder<D> {
+    dynamic: D,
+}
+
+impl<D> SecondOrder<D> {
+    pub fn new(dynamic: D) -> Self {
+        Self { dynamic }
+    }
+
+    pub fn dynamic(&self) -> &D {
+        &self.dynamic
+    }
+}
+
+impl<N, E, D> LocalDynamic<N, E> for SecondOrder<D>
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+    D: LocalDynamic<N, E>,
+{
+    fn states(&self) -> u32 {
+        self.dynamic.states()
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        self.dynamic.accepts(neighbors)
+    }
+
+    fn reads_grid_layout(&self) -> bool {
+        self.dynamic.reads_grid_layout()
+    }
+
+    fn accepts_grid(&self, width: u32, height: u32) -> bool {
+        self.dynamic.accepts_grid(width, height)
+    }
+
+    fn generation_period(&self) -> Option<u64> {
+        self.dynamic.generation_period()
+    }
+
+    fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N {
+        self.update_with_context(node, nodes, edges, &mut UpdateContext::default())
+    }
+
+    fn update_with_context(
+        &self,
+        node: &N,
+        nodes: &[N],
+        edges: Vec<&HyperEdge<E>>,
+        context: &mut UpdateContext,
+    ) -> N {
+        let states = self.dynamic.states();
+        let previous = context.previous.unwrap_or(0) % states;
+
+        let next = self
+            .dynamic
+            .update_with_context(node, nodes, edges, context)
+            .state();
+
+        N::from_state((next + states - previous) % states)
+    }
+
+    fn is_second_order(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamics::{context::NodeRng, implementations::life::LifeLike},
+        error::Error,
+        spaces::implementations::basic::{DiscreteState, HyperGraphHeap},
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    fn random_generation(rng: &mut NodeRng) -> Vec<DiscreteState> {
+        (0..16 * 16)
+            .map(|_| DiscreteState::from_state(rng.gen_bool(0.4) as u32))
+            .collect()
+    }
+
+    #[test]
+    fn backward_steps_restore_the_trajectory() {
+        let mut rng = NodeRng::new(3);
+        let (before, start) = (random_generation(&mut rng), random_generation(&mut rng));
+
+        let space = HyperGraphHeap::new_grid(&start, 16, 16, ());
+        let dynamic = SecondOrder::new(LifeLike::new(&[3], &[2, 3]));
+
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
+        system.set_previous_state(before.clone());
+
+        let mut trajectory = vec![system.space_state()];
+
+        for _ in 0..50 {
+            system.compute_sync();
+            trajectory.push(system.space_state());
+        }
+
+        for generation in (0..50).rev() {
+            system.try_compute_sync_backward().unwrap();
+
+            assert_eq!(system.space_state(), trajectory[generation]);
+            assert_eq!(system.generation(), generation as u64);
+        }
+
+        assert_eq!(system.previous_state(), Some(&before));
+    }
+
+    #[test]
+    fn backward_steps_need_the_previous_generation() {
+        let nodes = vec![DiscreteState::from_state(1); 16];
+        let space = HyperGraphHeap::new_grid(&nodes, 4, 4, ());
+        let dynamic = SecondOrder::new(LifeLike::new(&[3], &[2, 3]));
+
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
+
+        assert!(matches!(
+            system.try_compute_sync_backward(),
+            Err(Error::MissingPrevious)
+        ));
+        assert_eq!(system.space_state(), nodes);
+    }
+
+    #[test]
+    fn generation_zero_is_not_stepped_back() {
+        let mut rng = NodeRng::new(4);
+        let (before, start) = (random_generation(&mut rng), random_generation(&mut rng));
+
+        let space = HyperGraphHeap::new_grid(&start, 16, 16, ());
+        let dynamic = SecondOrder::new(LifeLike::new(&[3], &[2, 3]));
+
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
+        system.set_previous_state(before.clone());
+
+        assert!(matches!(
+            system.try_compute_sync_backward(),
+            Err(Error::FirstGeneration)
+        ));
+        assert_eq!(system.generation(), 0);
+        assert_eq!(system.space_state(), start);
+        assert_eq!(system.previous_state(), Some(&before));
+    }
+
+    #[test]
+    fn rewinding_keeps_the_previous_generation() {
+        let mut rng = NodeRng::new(5);
+        let (before, start) = (random_generation(&mut rng), random_generation(&mut rng));
+
+        let space = HyperGraphHeap::new_grid(&start, 16, 16, ());
+        let dynamic = SecondOrder::new(LifeLike::new(&[3], &[2, 3]));
+
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
+        system.set_previous_state(before);
+        system.enable_history(8);
+
+        let mut trajectory = vec![system.space_state()];
+
+        for _ in 0..5 {
+            system.compute_sync();
+            trajectory.push(system.space_state());
+        }
+
+        // the oldest step stays recorded, it holds the generation before trajectory[1]
+        assert_eq!(system.history_len(), 4);
+        assert_eq!(system.rewind(10), 4);
+        assert_eq!(system.space_state(), trajectory[1]);
+
+        for expected in &trajectory[2..] {
+            system.compute_sync();
+            assert_eq!(&system.space_state(), expected);
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::{context::NodeRng, local::LocalDynamic, statistics::TransitionRule},
+    error::Error,
+    spaces::local::{HyperEdge, Stateable},
+    system::backend::BackendKind,
+    third::codegen::{GridKernel, WgslRule},
+};
+
+/// Next state looked up from the sum of the node's and its neighbors' states.
+///
+/// The table covers sums up to `(neighbors + 1) * (states - 1)`; larger neighborhoods are
+/// refused.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TotalisticTable {
+    states: u32,
+    table: Vec<u32>,
+}
+
+impl TotalisticTable {
+    pub fn new(states: u32, table: Vec<u32>) -> Self {
+        assert!(states > 0, "tables need at least one state");
+        assert!(
+            table.iter().all(|&s| s < states),
+            "table entries must be states"
+        );
+
+        Self { states, table }
+    }
+
+    pub fn from_fn(states: u32, neighbors: usize, rule: impl FnMut(u32) -> u32) -> Self {
+        assert!(states > 0, "tables need at least one state");
+
+        let sums = (neighbors as u32 + 1) * (states - 1) + 1;
+
+        Self::new(states, (0..sums).map(rule).collect())
+    }
+
+    pub fn random(states: u32, neighbors: usize, rng: &mut NodeRng) -> Self {
+        Self::from_fn(states, neighbors, |_| rng.gen_range(states))
+    }
+
+    pub fn table(&self) -> &[u32] {
+        &self.table
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for TotalisticTable
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        self.states
+    }
+
+    fn supports(&self, _backend: BackendKind) -> bool {
+        true
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        (neighbors + 1)
+            .checked_mul(self.states as usize - 1)
+            .is_some_and(|sum| sum < self.table.len())
+    }
+
+    fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
+        let sum = node.state() + nodes.iter().map(|n| n.state()).sum::<u32>();
+
+        N::from_state(self.table[sum as usize])
+    }
+}
+
+/// Next state looked up from the node's own state and the sum of its neighbors' states, as in
+/// the life-like rules.
+///
+/// Each row covers sums up to `neighbors * (states - 1)`; larger neighborhoods are refused.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OuterTotalisticTable {
+    states: u32,
+    sums: usize,
+    table: Vec<u32>,
+}
+
+impl OuterTotalisticTable {
+    /// `table[state][sum]`, all rows of the same length.
+    pub fn new(states: u32, table: Vec<Vec<u32>>) -> Self {
+        assert!(states > 0, "tables need at least one state");
+        assert_eq!(table.len(), states as usize, "one row per state");
+
+        let sums = table[0].len();
+
+        assert!(
+            table.iter().all(|row| row.len() == sums),
+            "rows differ in length"
+        );
+        assert!(
+            table.iter().flatten().all(|&s| s < states),
+            "table entries must be states"
+        );
+
+        Self {
+            states,
+            sums,
+            table: table.concat(),
+        }
+    }
+
+    pub fn from_fn(states: u32, neighbors: usize, mut rule: impl FnMut(u32, u32) -> u32) -> Self {
+        assert!(states > 0, "tables need at least one state");
+
+        let sums = neighbors as u32 * (states - 1) + 1;
+
+        Self::new(
+            states,
+            (0..states)
+                .map(|state| (0..sums).map(|sum| rule(state, sum)).collect())
+                .collect(),
+        )
+    }
+
+    pub fn random(states: u32, neighbors: usize, rng: &mut NodeRng) -> Self {
+        Self::from_fn(states, neighbors, |_, _| rng.gen_range(states))
+    }
+
+    pub fn rows(&self) -> Vec<Vec<u32>> {
+        self.table
+            .chunks(self.sums)
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    pub fn next_state(&self, state: u32, sum: u32) -> u32 {
+        assert!((sum as usize) < self.sums, "neighbor sum outside the table");
+
+        self.table[state as usize * self.sums + sum as usize]
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for OuterTotalisticTable
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        self.states
+    }
+
+    fn supports(&self, _backend: BackendKind) -> bool {
+        true
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        neighbors
+            .checked_mul(self.states as usize - 1)
+            .is_some_and(|sum| sum < self.sums)
+    }
+
+    fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
+        let sum = nodes.iter().map(|n| n.state()).sum::<u32>();
+
+        N::from_state(self.next_state(node.state(), sum))
+    }
+}
+
+impl TransitionRule for TotalisticTable {
+    fn alphabet(&self) -> u32 {
+        self.states
+    }
+
+    fn neighborhood_size(&self) -> Result<usize, Error> {
+        (self.table.len() - 1)
+            .checked_div(self.states as usize - 1)
+            .ok_or(Error::UnknownNeighborhood {
+                states: self.states,
+            })
+    }
+
+    fn transition(&self, cells: &[u32]) -> u32 {
+        self.table[cells.iter().sum::<u32>() as usize]
+    }
+}
+
+impl TransitionRule for OuterTotalisticTable {
+    fn alphabet(&self) -> u32 {
+        self.states
+    }
+
+    fn neighborhood_size(&self) -> Result<usize, Error> {
+        (self.sums - 1)
+            .checked_div(self.states as usize - 1)
+            .map(|neighbors| neighbors + 1)
+            .ok_or(Error::UnknownNeighborhood {
+                states: self.states,
+            })
+    }
+
+    fn transition(&self, cells: &[u32]) -> u32 {
+        self.next_state(cells[0], cells[1..].iter().sum())
+    }
+}
+
+impl GridKernel for TotalisticTable {
+    fn kernel_rule(&self, _offsets: &[(i32, i32)]) -> (u32, WgslRule) {
+        let rule = WgslRule::Totalistic {
+            table: self.table.clone(),
+        };
+
+        (self.states, rule)
+    }
+}
+
+impl GridKernel for OuterTotalisticTable {
+    fn kernel_rule(&self, _offsets: &[(i32, i32)]) -> (u32, WgslRule) {
+        let rule = WgslRule::OuterTotalistic {
+            sums: self.sums,
+            table: self.table.clone(),
+        };
+
+        (self.states, rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamics::implementations::life::LifeLike,
+        spaces::implementations::basic::{DiscreteState, HyperGraphHeap},
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    fn accepts<D: LocalDynamic<DiscreteState, ()>>(dynamic: &D, neighbors: usize) -> bool {
+        dynamic.accepts(neighbors)
+    }
+
+    #[test]
+    fn tables_accept_the_neighborhoods_their_sums_cover() {
+        let totalistic = TotalisticTable::from_fn(3, 4, |sum| sum % 3);
+        let outer = OuterTotalisticTable::from_fn(3, 8, |state, sum| (state + sum) % 3);
+
+        assert_eq!(totalistic.table().len(), 11);
+        assert!(accepts(&totalistic, 2) && accepts(&totalistic, 4));
+        assert!(!accepts(&totalistic, 5));
+
+        assert!(accepts(&outer, 8) && !accepts(&outer, 9));
+
+        // a single state always sums to 0
+        assert!(accepts(&TotalisticTable::new(1, vec![0]), 1000));
+
+        let nodes = vec![DiscreteState::from_state(1); 16];
+        let space = HyperGraphHeap::new_grid(&nodes, 4, 4, ());
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(totalistic));
+
+        assert!(matches!(
+            system.try_compute_sync(),
+            Err(Error::Neighborhood { neighbors: 8, .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one state")]
+    fn stateless_tables_are_rejected() {
+        TotalisticTable::from_fn(0, 4, |_| 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one state")]
+    fn stateless_outer_tables_are_rejected() {
+        OuterTotalisticTable::new(0, vec![]);
+    }
+
+    #[test]
+    fn totalistic_sums_include_the_node() {
+        let parity = TotalisticTable::from_fn(2, 2, |sum| sum % 2);
+        let update = |node: u32, left: u32, right: u32| {
+            LocalDynamic::<DiscreteState, ()>::update(
+                &parity,
+                &DiscreteState::from_state(node),
+                &[left, right].map(DiscreteState::from_state),
+                vec![],
+            )
+            .state()
+        };
+
+        assert_eq!(update(1, 0, 0), 1);
+        assert_eq!(update(1, 1, 0), 0);
+        assert_eq!(update(0, 1, 0), 1);
+        assert_eq!(update(1, 1, 1), 1);
+    }
+
+    #[test]
+    fn outer_tables_reproduce_life() {
+        let life = OuterTotalisticTable::from_fn(2, 8, |state, sum| {
+            (sum == 3 || state == 1 && sum == 2) as u32
+        });
+
+        let mut rng = NodeRng::new(11);
+        let nodes = (0..16 * 16)
+            .map(|_| DiscreteState::from_state(rng.gen_bool(0.35) as u32))
+            .collect::<Vec<DiscreteState>>();
+
+        let mut table = DynamicalSystem::new(
+            Box::new(HyperGraphHeap::new_grid(&nodes, 16, 16, ())),
+            Box::new(life),
+        );
+        let mut reference = DynamicalSystem::new(
+            Box::new(HyperGraphHeap::new_grid(&nodes, 16, 16, ())),
+            Box::new(LifeLike::new(&[3], &[2, 3])),
+        );
+
+        for step in 0..20 {
+            table.compute_sync();
+            reference.compute_sync();
+
+            assert_eq!(table.space_state(), reference.space_state(), "step {step}");
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{
+    error::Error,
+    system::agents::{AgentAction, AgentRule, Turn},
+};
+
+fn spec_error(message: impl Into<String>) -> Error {
+    Error::Turmite(message.into())
+}
+
+/// Turmite given by its transition table, `table[state * colors + color]` being what an ant in
+/// `state` does on a cell of `color`.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Turmite {
+    colors: u32,
+    states: u32,
+    table: Vec<AgentAction>,
+}
+
+impl Turmite {
+    /// Checks the table has one action per state and color, each writing a color and switching
+    /// to a state of the turmite.
+    pub fn new(colors: u32, states: u32, table: Vec<AgentAction>) -> Result<Self, Error> {
+        if colors == 0 || states == 0 || table.len() != (colors * states) as usize {
+            return Err(spec_error(format!(
+                "{} actions for {colors} colors and {states} states",
+                table.len()
+            )));
+        }
+
+        if let Some(action) = table
+            .iter()
+            .find(|action| action.write >= colors || action.state >= states)
+        {
+            return Err(spec_error(format!(
+                "action {action:?} leaves the {colors} colors or {states} states"
+            )));
+        }
+
+        Ok(Self {
+            colors,
+            states,
+            table,
+        })
+    }
+
+    /// Langton's ant, the turmite `RL`.
+    pub fn langtons_ant() -> Self {
+        Self::from_spec("RL").unwrap()
+    }
+
+    /// Single-state turmite in the usual letter notation, one letter per color: an ant on a cell
+    /// of color `c` turns as the `c`-th letter says (`R`ight, `L`eft, `N`o turn or `U`-turn) and
+    /// paints the cell with color `c + 1`, wrapping around. Golly's `{{{1,2,0},{0,8,0}}}` tables
+    /// are accepted too.
+    pub fn from_spec(spec: &str) -> Result<Self, Error> {
+        let spec = spec.trim();
+
+        if spec.starts_with('{') {
+            return Self::from_golly(spec);
+        }
+
+        let turns = spec
+            .chars()
+            .map(|c| match c {
+                'R' => Ok(Turn::Right),
+                'L' => Ok(Turn::Left),
+                'N' => Ok(Turn::Straight),
+                'U' => Ok(Turn::Back),
+                other => Err(spec_error(format!("unknown turn '{other}'"))),
+            })
+            .collect::<Result<Vec<Turn>, Error>>()?;
+
+        if turns.len() < 2 {
+            return Err(spec_error("turmites need at least two colors"));
+        }
+
+        let colors = turns.len() as u32;
+
+        let table = turns
+            .into_iter()
+            .enumerate()
+            .map(|(color, turn)| AgentAction {
+                write: (color as u32 + 1) % colors,
+                turn,
+                state: 0,
+            })
+            .collect();
+
+        Self::new(colors, 1, table)
+    }
+
+    /// `{{{write, turn, next}, ...}, ...}` with one block per state and one triple per color,
+    /// turns being 1 (none), 2 (right), 4 (u-turn) or 8 (left).
+    fn from_golly(spec: &str) -> Result<Self, Error> {
+        let mut states = Vec::<Vec<[u32; 3]>>::new();
+        let mut triple = Vec::<u32>::new();
+        let mut number = String::new();
+        let mut depth = 0;
+
+        for c in spec.chars().filter(|c| !c.is_whitespace()) {
+            if c.is_ascii_digit() {
+                number.push(c);
+                continue;
+            }
+
+            if !number.is_empty() {
+                if depth != 3 {
+                    return Err(spec_error(format!("{number} is outside of a triple")));
+                }
+
+                let value = number
+                    .parse()
+                    .map_err(|_| spec_error(format!("{number} is out of range")))?;
+
+                triple.push(value);
+                number.clear();
+            }
+
+            match c {
+                '{' if depth < 3 => {
+                    depth += 1;
+
+                    if depth == 2 {
+                        states.push(Vec::new());
+                    }
+                }
+                '}' if depth > 0 => {
+                    if depth == 3 {
+                        let action = triple.as_slice().try_into().map_err(|_| {
+                            spec_error(format!("expected 3 numbers, found {triple:?}"))
+                        })?;
+
+                        states.last_mut().unwrap().push(action);
+                        triple.clear();
+                    }
+
+                    depth -= 1;
+                }
+                ',' => {}
+                other => return Err(spec_error(format!("unexpected '{other}'"))),
+            }
+        }
+
+        if depth != 0 || !number.is_empty() {
+            return Err(spec_error("unbalanced braces"));
+        }
+
+        let colors = states.first().map_or(0, Vec::len);
+
+        if colors == 0 || states.iter().any(|s| s.len() != colors) {
+            return Err(spec_error("every state needs one triple per color"));
+        }
+
+        let table = states
+            .iter()
+            .flatten()
+            .map(|&[write, turn, state]| {
+                let turn = match turn {
+                    1 => Turn::Straight,
+                    2 => Turn::Right,
+                    4 => Turn::Back,
+                    8 => Turn::Left,
+                    other => return Err(spec_error(format!("unknown turn {other}"))),
+                };
+
+                Ok(AgentAction { write, turn, state })
+            })
+            .collect::<Result<Vec<AgentAction>, Error>>()?;
+
+        Self::new(colors as u32, states.len() as u32, table)
+    }
+
+    pub fn colors(&self) -> u32 {
+        self.colors
+    }
+}
+
+impl AgentRule for Turmite {
+    fn states(&self) -> u32 {
+        self.states
+    }
+
+    fn act(&self, state: u32, cell: u32) -> AgentAction {
+        self.table[(state * self.colors + cell % self.colors) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_give_single_state_turmites() {
+        let ant = Turmite::langtons_ant();
+
+        assert_eq!((ant.colors(), ant.states()), (2, 1));
+        assert_eq!(
+            ant.act(0, 1),
+            AgentAction {
+                write: 0,
+                turn: Turn::Left,
+                state: 0,
+            }
+        );
+
+        let turmite = Turmite::from_spec(" LRRNU ").unwrap();
+        assert_eq!(turmite.act(0, 4).turn, Turn::Back);
+        assert_eq!(turmite.act(0, 4).write, 0);
+    }
+
+    #[test]
+    fn golly_tables_read_one_block_per_state() {
+        // Golly's "fibonacci" turmite
+        let turmite = Turmite::from_spec("{{{1,8,1},{1,8,1}},{{1,2,1},{0,1,0}}}").unwrap();
+
+        assert_eq!((turmite.colors(), turmite.states()), (2, 2));
+        assert_eq!(
+            turmite.act(1, 1),
+            AgentAction {
+                write: 0,
+                turn: Turn::Straight,
+                state: 0,
+            }
+        );
+        assert_eq!(turmite.act(1, 0).turn, Turn::Right);
+    }
+
+    #[test]
+    fn malformed_specs_are_rejected() {
+        for spec in [
+            "",
+            "R",
+            "RX",
+            "{{{1,2,0},{0,8,0}}}5",
+            "{{{1,2,0},{0,8,0}},7}",
+            "{7,{{1,2,0},{0,8,0}}}",
+            "{{{1,2,0},3,{0,8,0}}}",
+            "{{{1,2},{0,8,0}}}",
+            "{{{1,2,0,0},{0,8,0}}}",
+            "{{{1,2,0},{0,8,0}}",
+            "{{{1,2,0},{0,8,0}}}}",
+            "{{{{1,2,0}}}}",
+            "{{{1,3,0},{0,8,0}}}",
+            "{{{2,2,0},{0,8,0}}}",
+            "{{{1,2,1},{0,8,0}}}",
+            "{{{1,2,0},{0,8,0}},{{1,2,0}}}",
+            "{{{1,2,0},{0,8,0}},{{1,2;0},{0,8,0}}}",
+        ] {
+            assert!(
+                matches!(Turmite::from_spec(spec), Err(Error::Turmite(_))),
+                "{spec:?} was accepted"
+            );
+        }
+
+        assert!(Turmite::new(2, 1, vec![]).is_err());
+    }
+}
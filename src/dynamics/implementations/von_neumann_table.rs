@@ -0,0 +1,171 @@
+use std::{collections::BTreeMap, hash::Hash};
+
+use crate::{
+    dynamics::{local::LocalDynamic, statistics::TransitionRule},
+    error::Error,
+    spaces::{
+        implementations::grid::grid_neighborhood,
+        local::{HyperEdge, Stateable},
+    },
+};
+
+/// Rotation-symmetric von Neumann transition table in the style of Langton's loops: entries map
+/// the center and its N, E, S, W neighbors to the next center state, and neighborhoods without an
+/// entry keep their state.
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VonNeumannTable {
+    states: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::system::snapshot::pairs"))]
+    transitions: BTreeMap<[u32; 5], u32>,
+}
+
+impl VonNeumannTable {
+    pub fn new(states: u32) -> Self {
+        Self {
+            states,
+            transitions: BTreeMap::new(),
+        }
+    }
+
+    /// Adds `center, [n, e, s, w] -> next` together with its three rotations.
+    pub fn with_transition(mut self, center: u32, neighbors: [u32; 4], next: u32) -> Self {
+        for rotation in 0..4 {
+            let mut key = [center; 5];
+
+            for (i, state) in neighbors.iter().enumerate() {
+                key[1 + (i + rotation) % 4] = *state;
+            }
+
+            self.transitions.entry(key).or_insert(next);
+        }
+
+        self
+    }
+}
+
+/// Positions of the N, E, S and W neighbors in an ordered grid neighbor list of length `len`.
+pub(crate) fn von_neumann_positions(len: usize) -> Option<[usize; 4]> {
+    let offsets = grid_neighborhood(len)?;
+
+    let positions =
+        [(0, -1), (1, 0), (0, 1), (-1, 0)].map(|offset| offsets.iter().position(|&o| o == offset));
+
+    match positions {
+        [Some(n), Some(e), Some(s), Some(w)] => Some([n, e, s, w]),
+        _ => None,
+    }
+}
+
+pub(crate) fn von_neumann_states<N: Stateable>(nodes: &[N]) -> [u32; 4] {
+    von_neumann_positions(nodes.len())
+        .expect("von Neumann rules need a grid neighborhood with N, E, S and W neighbors")
+        .map(|position| nodes[position].state())
+}
+
+impl<N, E> LocalDynamic<N, E> for VonNeumannTable
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        self.states
+    }
+
+    fn accepts(&self, neighbors: usize) -> bool {
+        von_neumann_positions(neighbors).is_some()
+    }
+
+    fn reads_grid_layout(&self) -> bool {
+        true
+    }
+
+    fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
+        let [n, e, s, w] = von_neumann_states(nodes);
+
+        match self.transitions.get(&[node.state(), n, e, s, w]) {
+            Some(&next) => N::from_state(next),
+            None => node.clone(),
+        }
+    }
+}
+
+impl TransitionRule for VonNeumannTable {
+    fn alphabet(&self) -> u32 {
+        self.states
+    }
+
+    fn neighborhood_size(&self) -> Result<usize, Error> {
+        Ok(5)
+    }
+
+    /// Center, north, east, south, west.
+    fn transition(&self, cells: &[u32]) -> u32 {
+        let key = [cells[0], cells[1], cells[2], cells[3], cells[4]];
+
+        self.transitions.get(&key).copied().unwrap_or(cells[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::Error,
+        spaces::implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::one_dimensional_neighborhood,
+        },
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    #[test]
+    fn transitions_apply_in_every_rotation() {
+        let table = VonNeumannTable::new(2).with_transition(0, [1, 0, 0, 0], 1);
+
+        let mut nodes = vec![DiscreteState::from_state(0); 25];
+        nodes[12] = DiscreteState::from_state(1);
+
+        let space = HyperGraphHeap::new_grid(&nodes, 5, 5, ());
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(table));
+
+        system.compute_sync();
+
+        let states = system
+            .space_state()
+            .iter()
+            .map(|n| n.state())
+            .collect::<Vec<u32>>();
+
+        // the four von Neumann neighbors of the center light up, the diagonals do not
+        assert_eq!([states[7], states[11], states[13], states[17]], [1; 4]);
+        assert_eq!([states[6], states[8], states[16], states[18]], [0; 4]);
+    }
+
+    #[test]
+    fn rings_are_refused() {
+        // radius 2 and 3 rings have as many neighbors as von Neumann and hexagonal grids
+        for radius in 1..=3 {
+            let nodes = vec![DiscreteState::from_state(0); 8];
+            let space = HyperGraphHeap::new_grid_with_neighborhood(
+                &nodes,
+                8,
+                1,
+                &one_dimensional_neighborhood(radius),
+                (),
+            );
+            let mut system =
+                DynamicalSystem::new(Box::new(space), Box::new(VonNeumannTable::new(2)));
+
+            let neighbors = 2 * radius as usize;
+            assert!(
+                matches!(
+                    system.try_compute_sync(),
+                    Err(Error::Neighborhood { neighbors: n, .. }) if n == neighbors
+                ),
+                "radius {radius}"
+            );
+            assert_eq!(system.generation(), 0);
+        }
+    }
+}
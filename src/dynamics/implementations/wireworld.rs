@@ -0,0 +1,107 @@
+use std::hash::Hash;
+
+use crate::{
+    dynamics::local::LocalDynamic,
+    spaces::local::{HyperEdge, Stateable},
+};
+
+pub const EMPTY: u32 = 0;
+pub const ELECTRON_HEAD: u32 = 1;
+pub const ELECTRON_TAIL: u32 = 2;
+pub const CONDUCTOR: u32 = 3;
+
+#[derive(Clone, Hash, Eq, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WireWorld;
+
+impl WireWorld {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<N, E> LocalDynamic<N, E> for WireWorld
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    fn states(&self) -> u32 {
+        4
+    }
+
+    fn update(&self, node: &N, nodes: &[N], _edges: Vec<&HyperEdge<E>>) -> N {
+        match node.state() {
+            ELECTRON_HEAD => N::from_state(ELECTRON_TAIL),
+            ELECTRON_TAIL => N::from_state(CONDUCTOR),
+            CONDUCTOR => {
+                let heads = nodes.iter().filter(|n| n.state() == ELECTRON_HEAD).count();
+
+                if heads == 1 || heads == 2 {
+                    N::from_state(ELECTRON_HEAD)
+                } else {
+                    node.clone()
+                }
+            }
+            _ => node.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        spaces::implementations::basic::{DiscreteState, HyperGraphHeap},
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    const WIDTH: usize = 14;
+
+    /// Steps the drawing for 20 generations and tells whether an electron head ever reached
+    /// column `probe` of the middle row.
+    fn reaches(rows: [&str; 5], probe: usize) -> bool {
+        let nodes = rows
+            .iter()
+            .flat_map(|row| row.chars())
+            .map(|c| match c {
+                '#' => CONDUCTOR,
+                'H' => ELECTRON_HEAD,
+                't' => ELECTRON_TAIL,
+                _ => EMPTY,
+            })
+            .map(DiscreteState::from_state)
+            .collect::<Vec<DiscreteState>>();
+
+        let space = HyperGraphHeap::new_grid(&nodes, WIDTH as u32, rows.len() as u32, ());
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(WireWorld::new()));
+
+        (0..20).any(|_| {
+            system.compute_sync();
+            system.space_state()[2 * WIDTH + probe].state() == ELECTRON_HEAD
+        })
+    }
+
+    #[test]
+    fn diode_passes_electrons_one_way() {
+        let forward = [
+            "..............",
+            ".....##.......",
+            ".tH###.####...",
+            ".....##.......",
+            "..............",
+        ];
+
+        let backward = [
+            "..............",
+            ".....##.......",
+            "..####.##Ht...",
+            ".....##.......",
+            "..............",
+        ];
+
+        assert!(reaches(forward, 10));
+        // the electron gets into the diode from the right, but not out of it
+        assert!(reaches(backward, 7));
+        assert!(!reaches(backward, 2));
+    }
+}
@@ -1,6 +1,10 @@
 use std::hash::Hash;
 
-use crate::spaces::local::{HyperEdge, Stateable};
+use crate::{
+    dynamics::context::UpdateContext,
+    spaces::local::{HyperEdge, Stateable},
+    system::backend::BackendKind,
+};
 
 pub trait LocalDynamic<N, E>
 where
@@ -10,4 +14,55 @@ where
 {
     fn states(&self) -> u32;
     fn update(&self, node: &N, nodes: &[N], edges: Vec<&HyperEdge<E>>) -> N;
+
+    /// Called by the system for every node on every step. Rules that need randomness or to know
+    /// where and when they run override this; their `update` then falls back to a default
+    /// context, since outside of a step there is no node or generation to derive a stream from.
+    fn update_with_context(
+        &self,
+        node: &N,
+        nodes: &[N],
+        edges: Vec<&HyperEdge<E>>,
+        _context: &mut UpdateContext,
+    ) -> N {
+        self.update(node, nodes, edges)
+    }
+
+    /// Whether the rule reads `UpdateContext::previous`, so the system has to keep the generation
+    /// before the current one around.
+    fn is_second_order(&self) -> bool {
+        false
+    }
+
+    /// Whether `update` can handle a node with `neighbors` neighbors. The system reports
+    /// `Error::Neighborhood` for nodes a rule refuses instead of calling `update` on them.
+    fn accepts(&self, _neighbors: usize) -> bool {
+        true
+    }
+
+    /// Whether `update` reads the neighbors by their place in a grid layout, which is only
+    /// recognised from their count. The system then checks every node is linked as a grid built
+    /// with that layout, and reports `Error::Neighborhood` for the first one that is not.
+    fn reads_grid_layout(&self) -> bool {
+        false
+    }
+
+    /// Whether `update` can run on a `width` by `height` grid, asked of rules that read the grid
+    /// layout. The system reports `Error::GridSize` for grids a rule refuses.
+    fn accepts_grid(&self, _width: u32, _height: u32) -> bool {
+        true
+    }
+
+    /// Number of generations after which `update_with_context` applies the same map again, 1 for
+    /// rules that ignore the generation. Rules drawing from the node's random stream return
+    /// `None`, as a generation they repeat need not lead where it did before.
+    fn generation_period(&self) -> Option<u64> {
+        Some(1)
+    }
+
+    /// Whether `DynamicalSystem::step` may run the rule on `backend`. Every rule runs on the CPU;
+    /// rules with a WGSL kernel opt into the GPU.
+    fn supports(&self, backend: BackendKind) -> bool {
+        !backend.is_gpu()
+    }
 }
@@ -1,2 +1,6 @@
+pub mod combinators;
+pub mod context;
 pub mod implementations;
 pub mod local;
+pub mod rule_space;
+pub mod statistics;
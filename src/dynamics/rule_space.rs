@@ -0,0 +1,275 @@
+use crate::dynamics::{
+    context::NodeRng,
+    implementations::{
+        eca::ElementaryCellularAutomaton,
+        totalistic::{OuterTotalisticTable, TotalisticTable},
+    },
+};
+
+/// A finite family of rules numbered `0..size()`.
+///
+/// Rules related by a symmetry of the family (complementing the states, mirroring the
+/// neighborhood) behave the same up to relabelling, and `canonical` maps each one to the
+/// smallest index among them so that every class is visited once.
+pub trait RuleSpace {
+    type Rule;
+
+    fn size(&self) -> u64;
+    fn rule(&self, index: u64) -> Self::Rule;
+    fn canonical(&self, index: u64) -> u64;
+
+    fn indices(&self) -> impl Iterator<Item = u64> + '_ {
+        0..self.size()
+    }
+
+    fn canonical_indices(&self) -> impl Iterator<Item = u64> + '_ {
+        self.indices().filter(|&i| self.canonical(i) == i)
+    }
+
+    fn rules(&self) -> impl Iterator<Item = Self::Rule> + '_ {
+        self.indices().map(|i| self.rule(i))
+    }
+
+    fn canonical_rules(&self) -> impl Iterator<Item = Self::Rule> + '_ {
+        self.canonical_indices().map(|i| self.rule(i))
+    }
+
+    /// Uniform over all indices, so classes with more members come up more often.
+    fn sample_index(&self, rng: &mut NodeRng) -> u64 {
+        ((rng.next_u64() as u128 * self.size() as u128) >> 64) as u64
+    }
+
+    fn sample(&self, rng: &mut NodeRng) -> Self::Rule {
+        self.rule(self.sample_index(rng))
+    }
+
+    /// Uniform over indices of a class representative, drawn by rejection.
+    fn sample_canonical(&self, rng: &mut NodeRng) -> Self::Rule {
+        loop {
+            let index = self.sample_index(rng);
+
+            if self.canonical(index) == index {
+                return self.rule(index);
+            }
+        }
+    }
+}
+
+/// The 256 elementary rules under reflection and complementation, which leave 88 classes.
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug, Default)]
+pub struct ElementarySpace;
+
+impl ElementarySpace {
+    pub fn reflect(rule: u8) -> u8 {
+        (0..8).fold(0, |acc, i: u8| {
+            let mirrored = ((i & 1) << 2) | (i & 2) | (i >> 2);
+            acc | (((rule >> i) & 1) << mirrored)
+        })
+    }
+
+    pub fn complement(rule: u8) -> u8 {
+        (0..8).fold(0, |acc, i: u8| acc | ((!(rule >> (7 - i)) & 1) << i))
+    }
+}
+
+impl RuleSpace for ElementarySpace {
+    type Rule = ElementaryCellularAutomaton;
+
+    fn size(&self) -> u64 {
+        256
+    }
+
+    fn rule(&self, index: u64) -> Self::Rule {
+        ElementaryCellularAutomaton::new(index as u8)
+    }
+
+    fn canonical(&self, index: u64) -> u64 {
+        let rule = index as u8;
+        let complement = Self::complement(rule);
+
+        [
+            rule,
+            Self::reflect(rule),
+            complement,
+            Self::reflect(complement),
+        ]
+        .into_iter()
+        .min()
+        .unwrap() as u64
+    }
+}
+
+/// The 2^18 outer-totalistic rules on the Moore neighborhood, bit `n` of an index giving birth on
+/// `n` live neighbors and bit `9 + n` survival. Complementation pairs every rule with its
+/// black/white dual; the neighbor sum is already blind to reflections.
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug, Default)]
+pub struct LifeLikeSpace;
+
+impl LifeLikeSpace {
+    pub fn index(b_list: &[u32], s_list: &[u32]) -> u64 {
+        let birth = b_list.iter().fold(0, |acc, &n| acc | 1 << n);
+        let survival = s_list.iter().fold(0, |acc, &n| acc | 1 << (9 + n));
+
+        birth | survival
+    }
+
+    pub fn complement(index: u64) -> u64 {
+        (0..9).fold(0, |acc, n| {
+            let birth = !(index >> (9 + 8 - n)) & 1;
+            let survival = !(index >> (8 - n)) & 1;
+
+            acc | birth << n | survival << (9 + n)
+        })
+    }
+}
+
+impl RuleSpace for LifeLikeSpace {
+    type Rule = OuterTotalisticTable;
+
+    fn size(&self) -> u64 {
+        1 << 18
+    }
+
+    fn rule(&self, index: u64) -> Self::Rule {
+        OuterTotalisticTable::from_fn(2, 8, |state, sum| ((index >> (9 * state + sum)) & 1) as u32)
+    }
+
+    fn canonical(&self, index: u64) -> u64 {
+        index.min(Self::complement(index))
+    }
+}
+
+/// All `states`-state totalistic rules on nodes with `neighbors` neighbors, digit `s` of an index
+/// in base `states` being the next state for a sum of `s`. Complementation maps state `x` to
+/// `states - 1 - x`.
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+pub struct TotalisticSpace {
+    states: u32,
+    neighbors: usize,
+    sums: u32,
+}
+
+impl TotalisticSpace {
+    pub fn new(states: u32, neighbors: usize) -> Self {
+        assert!(states > 0, "rule spaces need at least one state");
+
+        let sums = (neighbors as u32 + 1) * (states - 1) + 1;
+
+        assert!(
+            (states as u64).checked_pow(sums).is_some(),
+            "rule space too large to index"
+        );
+
+        Self {
+            states,
+            neighbors,
+            sums,
+        }
+    }
+
+    fn digits(&self, index: u64) -> Vec<u32> {
+        let states = self.states as u64;
+
+        (0..self.sums)
+            .map(|s| (index / states.pow(s) % states) as u32)
+            .collect()
+    }
+
+    fn index(&self, digits: impl DoubleEndedIterator<Item = u32>) -> u64 {
+        digits
+            .rev()
+            .fold(0, |acc, d| acc * self.states as u64 + d as u64)
+    }
+
+    pub fn complement(&self, index: u64) -> u64 {
+        let digits = self.digits(index);
+
+        self.index(digits.iter().rev().map(|&d| self.states - 1 - d))
+    }
+}
+
+impl RuleSpace for TotalisticSpace {
+    type Rule = TotalisticTable;
+
+    fn size(&self) -> u64 {
+        (self.states as u64).pow(self.sums)
+    }
+
+    fn rule(&self, index: u64) -> Self::Rule {
+        TotalisticTable::new(self.states, self.digits(index))
+    }
+
+    fn canonical(&self, index: u64) -> u64 {
+        index.min(self.complement(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elementary_rules_fall_into_88_classes() {
+        assert_eq!(ElementarySpace.canonical_indices().count(), 88);
+
+        // rule 110 with its mirror image, its complement and both
+        for rule in [110, 124, 137, 193] {
+            assert_eq!(ElementarySpace.canonical(rule), 110);
+        }
+
+        for rule in 0..=255 {
+            assert_eq!(
+                ElementarySpace::reflect(ElementarySpace::reflect(rule)),
+                rule
+            );
+            assert_eq!(
+                ElementarySpace::complement(ElementarySpace::complement(rule)),
+                rule
+            );
+        }
+    }
+
+    #[test]
+    fn life_like_duals_pair_up() {
+        for index in LifeLikeSpace.indices() {
+            assert_eq!(
+                LifeLikeSpace::complement(LifeLikeSpace::complement(index)),
+                index
+            );
+        }
+
+        let life = LifeLikeSpace::index(&[3], &[2, 3]);
+        let dual = LifeLikeSpace::index(&[0, 1, 2, 3, 4, 7, 8], &[0, 1, 2, 3, 4, 6, 7, 8]);
+
+        assert_eq!(LifeLikeSpace::complement(life), dual);
+        assert_eq!(LifeLikeSpace.canonical(dual), life);
+
+        // b3/s23 read back from the table of its index
+        let rule = LifeLikeSpace.rule(life);
+        assert_eq!(rule.rows()[0][3], 1);
+        assert_eq!(rule.rows()[1][2..4], [1, 1]);
+        assert_eq!(rule.rows().concat().iter().sum::<u32>(), 3);
+    }
+
+    #[test]
+    fn totalistic_spaces_index_every_table() {
+        let space = TotalisticSpace::new(3, 2);
+
+        // sums run from 0 to 6
+        assert_eq!(space.size(), 3u64.pow(7));
+
+        for index in space.indices() {
+            assert_eq!(space.complement(space.complement(index)), index);
+            assert_eq!(
+                space.index(space.rule(index).table().iter().copied()),
+                index
+            );
+        }
+
+        // 16 rules of 2 states and 3 cells, of which 4 are their own complement
+        let binary = TotalisticSpace::new(2, 2);
+
+        assert_eq!(binary.size(), 16);
+        assert_eq!(binary.canonical_indices().count(), 10);
+    }
+}
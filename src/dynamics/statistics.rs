@@ -0,0 +1,160 @@
+use crate::error::Error;
+
+/// A rule seen as a map from every configuration of a fixed-size neighborhood to the next state,
+/// which is what the statistics below are computed over.
+pub trait TransitionRule {
+    fn alphabet(&self) -> u32;
+    /// Number of cells in a configuration, the center included, or an error when the rule does
+    /// not determine it.
+    fn neighborhood_size(&self) -> Result<usize, Error>;
+    /// Next state of a configuration given in the rule's own order: left, center and right for
+    /// elementary rules, the center followed by its neighbors otherwise.
+    fn transition(&self, cells: &[u32]) -> u32;
+}
+
+fn configurations(rule: &impl TransitionRule) -> Result<impl Iterator<Item = Vec<u32>>, Error> {
+    let (k, n) = (rule.alphabet(), rule.neighborhood_size()?);
+    let count = u32::try_from(n)
+        .ok()
+        .and_then(|n| (k as u64).checked_pow(n))
+        .filter(|&count| count > 0)
+        .ok_or(Error::Configurations {
+            states: k,
+            cells: n,
+        })?;
+
+    Ok((0..count).map(move |index| {
+        (0..n as u32)
+            .map(|i| (index / (k as u64).pow(i) % k as u64) as u32)
+            .collect()
+    }))
+}
+
+/// Fraction of configurations leading to a state other than `quiescent`.
+pub fn langton_lambda(rule: &impl TransitionRule, quiescent: u32) -> Result<f64, Error> {
+    let mut total = 0u64;
+    let mut active = 0u64;
+
+    for cells in configurations(rule)? {
+        total += 1;
+
+        if rule.transition(&cells) != quiescent {
+            active += 1;
+        }
+    }
+
+    Ok(active as f64 / total as f64)
+}
+
+/// Share of configurations leading to each state.
+pub fn output_distribution(rule: &impl TransitionRule) -> Result<Vec<f64>, Error> {
+    let mut counts = vec![0u64; rule.alphabet() as usize];
+
+    for cells in configurations(rule)? {
+        counts[rule.transition(&cells) as usize] += 1;
+    }
+
+    let total = counts.iter().sum::<u64>() as f64;
+
+    Ok(counts.into_iter().map(|c| c as f64 / total).collect())
+}
+
+/// Shannon entropy of the rule table's outputs, in bits.
+pub fn rule_entropy(rule: &impl TransitionRule) -> Result<f64, Error> {
+    Ok(output_distribution(rule)?
+        .into_iter()
+        .filter(|&p| p > 0.0)
+        .map(|p| -p * p.log2())
+        .sum())
+}
+
+/// States that stay put when surrounded by themselves.
+pub fn quiescent_states(rule: &impl TransitionRule) -> Result<Vec<u32>, Error> {
+    let n = rule.neighborhood_size()?;
+
+    Ok((0..rule.alphabet())
+        .filter(|&state| rule.transition(&vec![state; n]) == state)
+        .collect())
+}
+
+/// First-order estimate of Wuensche's Z-parameter: the share of configurations whose last
+/// (`from_left`) or first cell is the only value giving the observed next state once the other
+/// cells are known. Wuensche's Z counts how far a whole preimage can be rebuilt cell by cell,
+/// `Z = R_n + R_{n-1}(1 - R_n) + …`; this only looks one cell deep and keeps the first term, so
+/// it bounds Z from below. Ordered rules sit near 0, chaotic ones near 1 and complex ones in
+/// between.
+pub fn z_estimate_directed(rule: &impl TransitionRule, from_left: bool) -> Result<f64, Error> {
+    let k = rule.alphabet();
+    let position = if from_left {
+        rule.neighborhood_size()? - 1
+    } else {
+        0
+    };
+
+    let mut total = 0u64;
+    let mut determined = 0u64;
+
+    for mut cells in configurations(rule)? {
+        total += 1;
+
+        let value = cells[position];
+        let next = rule.transition(&cells);
+
+        let unique = (0..k).filter(|&other| other != value).all(|other| {
+            cells[position] = other;
+            rule.transition(&cells) != next
+        });
+
+        if unique {
+            determined += 1;
+        }
+    }
+
+    Ok(determined as f64 / total as f64)
+}
+
+/// The larger of the left and right first-order Z estimates.
+pub fn z_estimate(rule: &impl TransitionRule) -> Result<f64, Error> {
+    Ok(z_estimate_directed(rule, true)?.max(z_estimate_directed(rule, false)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamics::implementations::{
+        eca::ElementaryCellularAutomaton, totalistic::TotalisticTable,
+    };
+
+    #[test]
+    fn elementary_rules_match_known_values() {
+        let rule_0 = ElementaryCellularAutomaton::new(0);
+        let rule_30 = ElementaryCellularAutomaton::new(30);
+        let rule_90 = ElementaryCellularAutomaton::new(90);
+
+        assert_eq!(langton_lambda(&rule_0, 0).unwrap(), 0.0);
+        assert_eq!(langton_lambda(&rule_30, 0).unwrap(), 0.5);
+        assert_eq!(rule_entropy(&rule_90).unwrap(), 1.0);
+        assert_eq!(quiescent_states(&rule_90).unwrap(), vec![0]);
+        assert_eq!(z_estimate(&rule_0).unwrap(), 0.0);
+        assert_eq!(z_estimate(&rule_90).unwrap(), 1.0);
+        assert_eq!(z_estimate_directed(&rule_30, false).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn undetermined_or_huge_neighborhoods_are_errors() {
+        let single_state = TotalisticTable::new(1, vec![0]);
+        assert!(matches!(
+            langton_lambda(&single_state, 0),
+            Err(Error::UnknownNeighborhood { states: 1 })
+        ));
+
+        let huge = TotalisticTable::new(4, vec![0; 3 * 40 + 1]);
+        assert!(matches!(
+            rule_entropy(&huge),
+            Err(Error::Configurations {
+                states: 4,
+                cells: 40
+            })
+        ));
+    }
+}
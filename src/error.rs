@@ -0,0 +1,193 @@
+use std::{fmt::Display, path::PathBuf};
+
+use crate::system::backend::BackendKind;
+
+/// Failures the fallible (`try_`) variants of the stepping, device and image APIs report instead
+/// of panicking, along with those of the rule, weight and snapshot loaders.
+#[derive(Debug)]
+pub enum Error {
+    MissingNeighbors {
+        node: usize,
+    },
+    Neighborhood {
+        node: usize,
+        neighbors: usize,
+    },
+    MissingPrevious,
+    FirstGeneration,
+    Stochastic,
+    UnsupportedShape {
+        dimensions: usize,
+    },
+    NotAGrid {
+        node: usize,
+    },
+    GridSize {
+        width: u32,
+        height: u32,
+    },
+    NoAdapter,
+    Device(wgpu::RequestDeviceError),
+    ImageSize {
+        width: u32,
+        height: u32,
+        nodes: usize,
+    },
+    UnsupportedBackend {
+        backend: BackendKind,
+    },
+    Threshold {
+        node: usize,
+        threshold: u32,
+        degree: usize,
+    },
+    NoSink {
+        node: usize,
+    },
+    Turmite(String),
+    AgentPosition {
+        agent: usize,
+        position: usize,
+        nodes: usize,
+    },
+    AgentState {
+        agent: usize,
+        state: u32,
+        states: u32,
+    },
+    Configurations {
+        states: u32,
+        cells: usize,
+    },
+    UnknownNeighborhood {
+        states: u32,
+    },
+    Shader(String),
+    Kernel(String),
+    Gpu(String),
+    Io(std::io::Error),
+    Weights(String),
+    Image(image::ImageError),
+    RuleExtension(PathBuf),
+    RuleParse {
+        line: usize,
+        message: String,
+    },
+    Encoding(String),
+    Version {
+        found: u32,
+        expected: u32,
+    },
+    Snapshot(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingNeighbors { node } => write!(f, "node {node} has no neighbor entry"),
+            Error::Neighborhood { node, neighbors } => {
+                write!(
+                    f,
+                    "the dynamic cannot update node {node} with {neighbors} neighbors"
+                )
+            }
+            Error::MissingPrevious => write!(f, "backward steps need the previous generation"),
+            Error::Stochastic => write!(
+                f,
+                "the dynamic draws random numbers, so a repeated generation is no cycle"
+            ),
+            Error::FirstGeneration => write!(f, "generation 0 has no generation to step back to"),
+            Error::UnsupportedShape { dimensions } => {
+                write!(f, "{dimensions}D lattices are not supported, only 2D")
+            }
+            Error::NotAGrid { node } => {
+                write!(f, "node {node} is not linked like a cell of a periodic grid")
+            }
+            Error::GridSize { width, height } => {
+                write!(f, "the dynamic cannot run on a {width}×{height} grid")
+            }
+            Error::NoAdapter => write!(f, "failed to find an appropriate adapter"),
+            Error::Device(err) => write!(f, "failed to create device: {err}"),
+            Error::ImageSize {
+                width,
+                height,
+                nodes,
+            } => write!(f, "a {width}×{height} image does not fit {nodes} nodes"),
+            Error::UnsupportedBackend { backend } => {
+                write!(f, "the dynamic does not run on the {backend:?} backend")
+            }
+            Error::Threshold {
+                node,
+                threshold,
+                degree,
+            } => write!(
+                f,
+                "node {node} topples at {threshold} grains but gives one to each of {degree} neighbors"
+            ),
+            Error::NoSink { node } => {
+                write!(f, "grains toppling from node {node} never reach a sink")
+            }
+            Error::Configurations { states, cells } => write!(
+                f,
+                "{states} states over {cells} cells give too many configurations to enumerate"
+            ),
+            Error::UnknownNeighborhood { states } => write!(
+                f,
+                "a totalistic table over {states} state(s) does not determine its neighborhood"
+            ),
+            Error::Turmite(message) => write!(f, "invalid turmite: {message}"),
+            Error::AgentPosition {
+                agent,
+                position,
+                nodes,
+            } => write!(
+                f,
+                "agent {agent} stands on node {position}, outside the {nodes} nodes of the space"
+            ),
+            Error::AgentState {
+                agent,
+                state,
+                states,
+            } => write!(
+                f,
+                "agent {agent} is in state {state}, but its rule has {states} states"
+            ),
+            Error::Shader(message) => write!(f, "invalid WGSL: {message}"),
+            Error::Kernel(message) => write!(f, "cannot generate the kernel: {message}"),
+            Error::Gpu(message) => write!(f, "GPU step failed: {message}"),
+            Error::Io(err) => write!(f, "I/O failure: {err}"),
+            Error::Weights(message) => write!(f, "invalid weights: {message}"),
+            Error::Image(err) => write!(f, "failed to write image: {err}"),
+            Error::RuleExtension(path) => {
+                write!(f, "{}: expected a .table or .tree file", path.display())
+            }
+            Error::RuleParse { line, message } => write!(f, "line {line}: {message}"),
+            Error::Encoding(message) => write!(f, "failed to encode snapshot: {message}"),
+            Error::Version { found, expected } => write!(
+                f,
+                "snapshot version {found} is not supported (expected {expected})"
+            ),
+            Error::Snapshot(message) => write!(f, "malformed snapshot: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<wgpu::RequestDeviceError> for Error {
+    fn from(err: wgpu::RequestDeviceError) -> Self {
+        Error::Device(err)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<image::ImageError> for Error {
+    fn from(err: image::ImageError) -> Self {
+        Error::Image(err)
+    }
+}
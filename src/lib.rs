@@ -1,4 +1,5 @@
 pub mod dynamics;
+pub mod error;
 pub mod spaces;
 pub mod system;
 pub mod third;
@@ -6,6 +6,7 @@ use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIter
 use crate::spaces::local::{HyperEdge, LocalHyperGraph, Stateable};
 
 #[derive(Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DiscreteState {
     state: u32,
 }
@@ -35,6 +36,7 @@ impl Stateable for DiscreteState {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HyperGraphHeap<N, E, P>
 where
     N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
@@ -43,7 +45,18 @@ where
 {
     nodes: Vec<N>,
 
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            with = "crate::system::snapshot::sorted",
+            bound(
+                serialize = "E: serde::Serialize",
+                deserialize = "E: serde::Deserialize<'de>"
+            )
+        )
+    )]
     edges: HashMap<usize, HyperEdge<E>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::system::snapshot::sorted"))]
     node_neighbors: HashMap<usize, Vec<usize>>,
 
     payload: P,
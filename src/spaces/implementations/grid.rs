@@ -1,7 +1,29 @@
 use super::basic::HyperGraphHeap;
-use crate::spaces::local::Stateable;
+use crate::{error::Error, spaces::local::Stateable};
 use std::{collections::HashMap, fmt::Debug, hash::Hash};
 
+/// Neighbor offsets `(dx, dy)` in the order `new_grid` links them: NW, N, NE, W, E, SW, S, SE.
+pub const MOORE_NEIGHBORHOOD: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// N, W, E, S.
+pub const VON_NEUMANN_NEIGHBORHOOD: [(i32, i32); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Hexagonal lattice emulated on the square grid (Moore without NE and SW): NW, N, W, E, S, SE.
+pub const HEXAGONAL_NEIGHBORHOOD: [(i32, i32); 6] =
+    [(-1, -1), (0, -1), (-1, 0), (1, 0), (0, 1), (1, 1)];
+
+/// W, E. Use with a height of 1 to get a ring.
+pub const ONE_DIMENSIONAL_NEIGHBORHOOD: [(i32, i32); 2] = [(-1, 0), (1, 0)];
+
 impl<N, E> HyperGraphHeap<N, E, (u32, u32)>
 where
     N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
@@ -16,6 +38,17 @@ where
         width: u32,
         height: u32,
         default_edge: E,
+    ) -> HyperGraphHeap<N, E, (u32, u32)> {
+        Self::new_grid_with_neighborhood(nodes, width, height, &MOORE_NEIGHBORHOOD, default_edge)
+    }
+
+    /// Periodic grid whose nodes are linked to `neighborhood` offsets, in that order.
+    pub fn new_grid_with_neighborhood(
+        nodes: &Vec<N>,
+        width: u32,
+        height: u32,
+        neighborhood: &[(i32, i32)],
+        default_edge: E,
     ) -> HyperGraphHeap<N, E, (u32, u32)> {
         let mut edges = HashMap::<usize, Vec<(Vec<usize>, E)>>::new();
 
@@ -25,41 +58,14 @@ where
 
                 let mut local_neighborhood = Vec::<(Vec<usize>, E)>::new();
 
-                for dy in [-1, 0, 1] {
-                    for dx in [-1, 0, 1] {
-                        if dx == 0 && dy == 0 {
-                            continue;
-                        }
-
-                        let neighbor_node_x = current_node_x as i32 + dx;
-
-                        let neighbor_node_x = if neighbor_node_x < 0 {
-                            width as i32 + dx
-                        } else if neighbor_node_x >= width as i32 {
-                            0
-                        } else {
-                            neighbor_node_x
-                        };
-
-                        let neighbor_node_y = current_node_y as i32 + dy;
-
-                        let neighbor_node_y = if neighbor_node_y < 0 {
-                            height as i32 + dy
-                        } else if neighbor_node_y >= height as i32 {
-                            0
-                        } else {
-                            neighbor_node_y
-                        };
-
-                        let neighbor_index = Self::xy_to_index(
-                            width,
-                            neighbor_node_x as u32,
-                            neighbor_node_y as u32,
-                        );
-
-                        local_neighborhood
-                            .push((vec![neighbor_index as usize], default_edge.clone()));
-                    }
+                for (dx, dy) in neighborhood {
+                    let neighbor_node_x = (current_node_x as i32 + dx).rem_euclid(width as i32);
+                    let neighbor_node_y = (current_node_y as i32 + dy).rem_euclid(height as i32);
+
+                    let neighbor_index =
+                        Self::xy_to_index(width, neighbor_node_x as u32, neighbor_node_y as u32);
+
+                    local_neighborhood.push((vec![neighbor_index as usize], default_edge.clone()));
                 }
 
                 edges.insert(index as usize, local_neighborhood);
@@ -69,3 +75,152 @@ where
         HyperGraphHeap::from_nodes_and_edges(nodes.to_owned(), edges, &(width, height))
     }
 }
+
+/// The `radius` cells on either side, left to right. Use with a height of 1 to get a ring.
+pub fn one_dimensional_neighborhood(radius: i32) -> Vec<(i32, i32)> {
+    (-radius..=radius)
+        .filter(|&dx| dx != 0)
+        .map(|dx| (dx, 0))
+        .collect()
+}
+
+/// Offsets of an ordered neighbor list built by the grid constructors, recognised by its length.
+/// Other links of the same length are only told apart by `grid_layout`.
+pub fn grid_neighborhood(len: usize) -> Option<&'static [(i32, i32)]> {
+    match len {
+        8 => Some(&MOORE_NEIGHBORHOOD),
+        6 => Some(&HEXAGONAL_NEIGHBORHOOD),
+        4 => Some(&VON_NEUMANN_NEIGHBORHOOD),
+        2 => Some(&ONE_DIMENSIONAL_NEIGHBORHOOD),
+        _ => None,
+    }
+}
+
+/// Offsets of the neighborhood a `width` by `height` grid was built with, recognised by the
+/// neighbor count of its first node and checked against the neighbor list of every node.
+pub fn grid_offsets(
+    node_neighbors: &HashMap<usize, Vec<usize>>,
+    width: u32,
+    height: u32,
+) -> Result<Vec<Vec<i32>>, Error> {
+    let len = node_neighbors
+        .get(&0)
+        .map_or(0, |neighbors| neighbors.len());
+
+    let layout = grid_neighborhood(len).ok_or(Error::NotAGrid { node: 0 })?;
+    let (w, h) = (width as i32, height as i32);
+
+    for node in 0..width as usize * height as usize {
+        let (x, y) = (node as i32 % w, node as i32 / w);
+
+        let expected = layout
+            .iter()
+            .map(|(dx, dy)| ((y + dy).rem_euclid(h) * w + (x + dx).rem_euclid(w)) as usize);
+
+        if !node_neighbors
+            .get(&node)
+            .is_some_and(|neighbors| neighbors.iter().copied().eq(expected))
+        {
+            return Err(Error::NotAGrid { node });
+        }
+    }
+
+    Ok(layout.iter().map(|&(dx, dy)| vec![dx, dy]).collect())
+}
+
+/// Width and height of a grid built by the grid constructors, with the layout recognised from
+/// the neighbor count checked against the neighbor list of each of its `nodes`. Node 0 sits in
+/// the top left corner, so its west neighbor ends the first row and gives the width.
+pub fn grid_layout(
+    node_neighbors: &HashMap<usize, Vec<usize>>,
+    nodes: usize,
+) -> Result<(u32, u32), Error> {
+    let first = node_neighbors.get(&0).ok_or(Error::NotAGrid { node: 0 })?;
+    let layout = grid_neighborhood(first.len()).ok_or(Error::NotAGrid { node: 0 })?;
+
+    let west = layout
+        .iter()
+        .position(|&offset| offset == (-1, 0))
+        .expect("every grid layout has a west neighbor");
+    let width = first[west] + 1;
+
+    if !nodes.is_multiple_of(width) {
+        return Err(Error::NotAGrid { node: 0 });
+    }
+
+    let (width, height) = (width as u32, (nodes / width) as u32);
+
+    grid_offsets(node_neighbors, width, height)?;
+
+    Ok((width, height))
+}
+
+/// Positions of `offsets` within the ordered neighborhood `layout`, if all of them are present.
+pub fn neighborhood_positions(layout: &[(i32, i32)], offsets: &[(i32, i32)]) -> Option<Vec<usize>> {
+    offsets
+        .iter()
+        .map(|offset| layout.iter().position(|o| o == offset))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spaces::{implementations::basic::DiscreteState, local::LocalHyperGraph};
+
+    fn grid(width: u32, height: u32, neighborhood: &[(i32, i32)]) -> HashMap<usize, Vec<usize>> {
+        let nodes = vec![DiscreteState::from_state(0); (width * height) as usize];
+
+        HyperGraphHeap::new_grid_with_neighborhood(&nodes, width, height, neighborhood, ())
+            .node_neighbors()
+            .clone()
+    }
+
+    #[test]
+    fn offsets_of_every_layout_are_recovered() {
+        for layout in [
+            &MOORE_NEIGHBORHOOD[..],
+            &VON_NEUMANN_NEIGHBORHOOD[..],
+            &HEXAGONAL_NEIGHBORHOOD[..],
+            &ONE_DIMENSIONAL_NEIGHBORHOOD[..],
+        ] {
+            let offsets = grid_offsets(&grid(7, 5, layout), 7, 5).unwrap();
+            assert_eq!(grid_layout(&grid(7, 5, layout), 35).unwrap(), (7, 5));
+
+            assert!(offsets
+                .iter()
+                .map(|o| (o[0], o[1]))
+                .eq(layout.iter().copied()));
+        }
+    }
+
+    #[test]
+    fn other_links_are_not_taken_for_a_grid() {
+        // four neighbors, but not the von Neumann ones
+        let ring = grid(9, 1, &one_dimensional_neighborhood(2));
+        assert!(matches!(
+            grid_offsets(&ring, 9, 1),
+            Err(Error::NotAGrid { node: 0 })
+        ));
+
+        let mut rewired = grid(7, 5, &MOORE_NEIGHBORHOOD);
+        rewired.get_mut(&12).unwrap().swap(0, 7);
+        assert!(matches!(
+            grid_offsets(&rewired, 7, 5),
+            Err(Error::NotAGrid { node: 12 })
+        ));
+
+        assert!(matches!(
+            grid_offsets(&grid(7, 5, &[(2, 0)]), 7, 5),
+            Err(Error::NotAGrid { node: 0 })
+        ));
+
+        assert!(matches!(
+            grid_layout(&ring, 9),
+            Err(Error::NotAGrid { node: 0 })
+        ));
+
+        // a payload that does not match the nodes
+        assert!(grid_offsets(&grid(7, 5, &MOORE_NEIGHBORHOOD), 5, 7).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use std::hash::Hash;
+
+use crate::spaces::local::Stateable;
+
+/// Alpha channel of a neural cellular automaton cell, telling live cells from empty space.
+pub const ALPHA_CHANNEL: usize = 3;
+
+/// Cell of a neural cellular automaton: RGBA followed by hidden channels.
+///
+/// Seen as a discrete state, a cell is 1 when its alpha exceeds 0.1 and 0 otherwise.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NeuralState {
+    channels: Vec<f32>,
+    // alive mask of the update half of a step, consumed by the masking half
+    alive_before: bool,
+}
+
+impl NeuralState {
+    pub fn new(channels: Vec<f32>) -> Self {
+        Self {
+            channels,
+            alive_before: false,
+        }
+    }
+
+    pub(crate) fn with_alive_before(channels: Vec<f32>, alive_before: bool) -> Self {
+        Self {
+            channels,
+            alive_before,
+        }
+    }
+
+    pub(crate) fn alive_before(&self) -> bool {
+        self.alive_before
+    }
+
+    pub fn channels(&self) -> &[f32] {
+        &self.channels
+    }
+
+    /// Missing channels read as 0.
+    pub fn channel(&self, index: usize) -> f32 {
+        self.channels.get(index).copied().unwrap_or(0.0)
+    }
+
+    pub fn alpha(&self) -> f32 {
+        self.channel(ALPHA_CHANNEL)
+    }
+
+    /// Color clamped to `[0, 1]`, premultiplied by alpha as in the trained models.
+    pub fn rgba(&self) -> [f32; 4] {
+        [0, 1, 2, ALPHA_CHANNEL].map(|c| self.channel(c).clamp(0.0, 1.0))
+    }
+}
+
+// channels compare bitwise so that states can be hashed, as the spaces require
+impl PartialEq for NeuralState {
+    fn eq(&self, other: &Self) -> bool {
+        self.alive_before == other.alive_before
+            && self.channels.len() == other.channels.len()
+            && self
+                .channels
+                .iter()
+                .zip(&other.channels)
+                .all(|(a, b)| a.to_bits() == b.to_bits())
+    }
+}
+
+impl Eq for NeuralState {}
+
+impl Hash for NeuralState {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.alive_before.hash(state);
+
+        for channel in &self.channels {
+            channel.to_bits().hash(state);
+        }
+    }
+}
+
+impl Stateable for NeuralState {
+    fn state(&self) -> u32 {
+        (self.alpha() > 0.1) as u32
+    }
+
+    fn set_state(&mut self, state: u32) {
+        if self.channels.len() <= ALPHA_CHANNEL {
+            self.channels.resize(ALPHA_CHANNEL + 1, 0.0);
+        }
+
+        self.channels[ALPHA_CHANNEL] = state as f32;
+    }
+
+    fn from_state(state: u32) -> Self {
+        let mut node = Self::default();
+        node.set_state(state);
+        node
+    }
+}
@@ -2,7 +2,10 @@ use std::hash::Hash;
 
 use wgpu::ShaderSource;
 
-use crate::third::wgpu::{GpuDevice, Image, Kernel};
+use crate::{
+    error::Error,
+    third::wgpu::{GpuDevice, Image, Kernel, Real},
+};
 
 use super::local::Stateable;
 
@@ -15,20 +18,108 @@ where
     E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
 {
     fn shape(&self) -> Vec<usize>;
-    fn observation_neighbors(&self) -> Vec<Vec<i32>>;
-    fn update_wgsl_code(&self) -> ShaderSource;
+    /// Offsets linking each cell to its neighbors, or an error when the space is not a lattice.
+    fn observation_neighbors(&self) -> Result<Vec<Vec<i32>>, Error>;
+    fn update_wgsl_code(&self) -> Result<ShaderSource<'_>, Error>;
+    /// Contents of the `params` uniform: the lattice width and height, then the rule.
+    fn wgsl_params(&self) -> Vec<u32>;
+    /// Lookup table of the rule, uploaded as a read-only storage buffer rather than inlined in
+    /// the shader. Empty for kernels without one.
+    fn wgsl_table(&self) -> Result<Vec<u32>, Error> {
+        Ok(Vec::new())
+    }
+
+    /// Binds the image as `input` (0), a same-sized `result` (1), the neighbor offsets (2),
+    /// `wgsl_params` (3) and `wgsl_table` (4) to `shader`.
     fn wgsl_compute<'a>(
         &self,
-        kernel: &'a Kernel,
+        _kernel: &'a Kernel,
         image: &'a Image,
         device: &'a GpuDevice,
         shader: wgpu::ShaderSource,
-    ) -> (
-        Image,
-        u64,
-        wgpu::Buffer,
-        wgpu::Buffer,
-        wgpu::BindGroup,
-        wgpu::ComputePipeline,
-    );
+    ) -> Result<
+        (
+            Image,
+            u64,
+            wgpu::Buffer,
+            wgpu::Buffer,
+            wgpu::BindGroup,
+            wgpu::ComputePipeline,
+        ),
+        Error,
+    > {
+        let output = Image {
+            data: Vec::new(),
+            width: image.width,
+            height: image.height,
+        };
+
+        let observation = self.observation_neighbors()?;
+
+        let neighbors = observation.iter().flatten().copied().collect::<Vec<i32>>();
+
+        let output_size =
+            (output.width * output.height * std::mem::size_of::<Real>() as u32) as u64;
+        let params = self.wgsl_params();
+        let params_data = bytemuck::cast_slice(&params);
+
+        // create input and output buffers
+        let input_buffer = device.create_data_buffer("input", bytemuck::cast_slice(&image.data));
+        let result_buffer = device.create_buffer("result", output_size);
+        let neighbors_buffer =
+            device.create_data_buffer("neighbors", bytemuck::cast_slice(&neighbors[..]));
+        let output_buffer = device.create_output_buffer("output", output_size);
+
+        let params_buffer = device.create_uniform_buffer("params", params_data);
+        let table_buffer = device.create_data_buffer("table", &table_data(self.wgsl_table()?));
+
+        // create bind group and compute pipeline
+        let (bind_group, compute_pipeline) = device.create_compute_pipeline(
+            &[
+                (
+                    &input_buffer,
+                    4,
+                    wgpu::BufferBindingType::Storage { read_only: false },
+                ),
+                (
+                    &result_buffer,
+                    4,
+                    wgpu::BufferBindingType::Storage { read_only: false },
+                ),
+                (
+                    &neighbors_buffer,
+                    8,
+                    wgpu::BufferBindingType::Storage { read_only: false },
+                ),
+                (
+                    &params_buffer,
+                    params_data.len() as u64,
+                    wgpu::BufferBindingType::Uniform,
+                ),
+                (
+                    &table_buffer,
+                    4,
+                    wgpu::BufferBindingType::Storage { read_only: true },
+                ),
+            ],
+            shader,
+        );
+        Ok((
+            output,
+            output_size,
+            result_buffer,
+            output_buffer,
+            bind_group,
+            compute_pipeline,
+        ))
+    }
+}
+
+/// Bytes of the `table` buffer, padded to one entry since bindings cannot be empty.
+pub(crate) fn table_data(mut table: Vec<u32>) -> Vec<u8> {
+    if table.is_empty() {
+        table.push(0);
+    }
+
+    bytemuck::cast_slice(&table).to_vec()
 }
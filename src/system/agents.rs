@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use crate::{error::Error, spaces::implementations::grid::neighborhood_positions};
+
+/// Clockwise directions on square grids: N, E, S, W.
+const SQUARE_COMPASS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Clockwise directions on the hexagonal grid, starting north.
+const HEXAGONAL_COMPASS: [(i32, i32); 6] = [(0, -1), (1, 0), (1, 1), (0, 1), (-1, 0), (-1, -1)];
+
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Turn {
+    Straight,
+    Right,
+    Back,
+    Left,
+}
+
+impl Turn {
+    /// Clockwise steps around a compass of `directions` entries.
+    pub fn steps(&self, directions: usize) -> usize {
+        match self {
+            Turn::Straight => 0,
+            Turn::Right => 1,
+            Turn::Back => directions / 2,
+            Turn::Left => directions - 1,
+        }
+    }
+}
+
+/// What an agent does on the node it occupies: write `write` there, turn, switch to `state` and
+/// step forward.
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AgentAction {
+    pub write: u32,
+    pub turn: Turn,
+    pub state: u32,
+}
+
+pub trait AgentRule: Send + Sync {
+    /// Number of internal agent states.
+    fn states(&self) -> u32;
+    fn act(&self, state: u32, cell: u32) -> AgentAction;
+}
+
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+pub struct Agent {
+    /// Index of the occupied node.
+    pub position: usize,
+    /// Index into the layer's compass.
+    pub direction: usize,
+    pub state: u32,
+}
+
+impl Agent {
+    pub fn new(position: usize, direction: usize) -> Self {
+        Self {
+            position,
+            direction,
+            state: 0,
+        }
+    }
+}
+
+/// Agents moving over the nodes of a space, stepped with `DynamicalSystem::compute_agents`.
+///
+/// The compass lists, in clockwise order, the positions within each node's neighbor list that an
+/// agent can head towards, so it only makes sense on spaces whose neighbor lists share a layout.
+#[derive(Clone, Debug)]
+pub struct AgentLayer<R: AgentRule> {
+    rule: R,
+    compass: Vec<usize>,
+    agents: Vec<Agent>,
+}
+
+impl<R: AgentRule> AgentLayer<R> {
+    pub fn new(rule: R, compass: Vec<usize>) -> Self {
+        Self {
+            rule,
+            compass,
+            agents: Vec::new(),
+        }
+    }
+
+    /// Layer for a grid built with the `layout` neighborhood. Agents head N, E, S or W, or along
+    /// the six directions of the hexagonal layout.
+    pub fn on_grid(rule: R, layout: &[(i32, i32)]) -> Option<Self> {
+        let directions: &[(i32, i32)] = if layout.len() == HEXAGONAL_COMPASS.len() {
+            &HEXAGONAL_COMPASS
+        } else {
+            &SQUARE_COMPASS
+        };
+
+        Some(Self::new(rule, neighborhood_positions(layout, directions)?))
+    }
+
+    pub fn with_agent(mut self, agent: Agent) -> Self {
+        self.agents.push(agent);
+        self
+    }
+
+    pub fn add_agent(&mut self, agent: Agent) {
+        self.agents.push(agent);
+    }
+
+    pub fn rule(&self) -> &R {
+        &self.rule
+    }
+
+    pub fn compass(&self) -> &[usize] {
+        &self.compass
+    }
+
+    pub fn agents(&self) -> &[Agent] {
+        &self.agents
+    }
+
+    /// Moves every agent once, in order, each one seeing what the ones before it wrote.
+    pub fn step(&mut self, states: &mut [u32], neighbors: &HashMap<usize, Vec<usize>>) {
+        if let Err(err) = self.try_step(states, neighbors) {
+            panic!("{err}");
+        }
+    }
+
+    /// Like `step`, but reports an agent standing outside `states` or in a state its rule does
+    /// not have, and a node whose neighbor list is missing or lacks the heading, instead of
+    /// panicking. The agents are left untouched on error, while `states` keeps what the agents
+    /// before the failing one wrote.
+    pub fn try_step(
+        &mut self,
+        states: &mut [u32],
+        neighbors: &HashMap<usize, Vec<usize>>,
+    ) -> Result<(), Error> {
+        let directions = self.compass.len();
+        let mut agents = self.agents.clone();
+
+        for (i, agent) in agents.iter_mut().enumerate() {
+            let cell = *states.get(agent.position).ok_or(Error::AgentPosition {
+                agent: i,
+                position: agent.position,
+                nodes: states.len(),
+            })?;
+
+            if agent.state >= self.rule.states() {
+                return Err(Error::AgentState {
+                    agent: i,
+                    state: agent.state,
+                    states: self.rule.states(),
+                });
+            }
+
+            let action = self.rule.act(agent.state, cell);
+            let links = neighbors
+                .get(&agent.position)
+                .ok_or(Error::MissingNeighbors {
+                    node: agent.position,
+                })?;
+
+            let (direction, next) = (agent.direction + action.turn.steps(directions))
+                .checked_rem(directions)
+                .and_then(|direction| Some((direction, *links.get(self.compass[direction])?)))
+                .ok_or(Error::Neighborhood {
+                    node: agent.position,
+                    neighbors: links.len(),
+                })?;
+
+            states[agent.position] = action.write;
+
+            agent.state = action.state;
+            agent.direction = direction;
+            agent.position = next;
+        }
+
+        self.agents = agents;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamics::implementations::{life::LifeLike, turmite::Turmite},
+        spaces::{
+            implementations::{
+                basic::{DiscreteState, HyperGraphHeap},
+                grid::VON_NEUMANN_NEIGHBORHOOD,
+            },
+            local::Stateable,
+        },
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    type Grid = HyperGraphHeap<DiscreteState, (), (u32, u32)>;
+
+    fn system(nodes: &Vec<DiscreteState>) -> DynamicalSystem<Grid, LifeLike, DiscreteState, ()> {
+        let space =
+            HyperGraphHeap::new_grid_with_neighborhood(nodes, 4, 4, &VON_NEUMANN_NEIGHBORHOOD, ());
+
+        DynamicalSystem::new(Box::new(space), Box::new(LifeLike::new(&[3], &[2, 3])))
+    }
+
+    fn ants(agents: &[Agent]) -> AgentLayer<Turmite> {
+        let mut layer =
+            AgentLayer::on_grid(Turmite::langtons_ant(), &VON_NEUMANN_NEIGHBORHOOD).unwrap();
+
+        for agent in agents {
+            layer.add_agent(agent.clone());
+        }
+
+        layer
+    }
+
+    #[test]
+    fn ants_flip_the_node_they_leave() {
+        let nodes = vec![DiscreteState::from_state(0); 16];
+        let mut system = system(&nodes);
+        let mut layer = ants(&[Agent::new(5, 0)]);
+
+        system.try_compute_agents(&mut layer).unwrap();
+
+        // a white node turns the ant right, from north to east
+        assert_eq!(system.space_state()[5].state(), 1);
+        assert_eq!(layer.agents(), [Agent::new(6, 1)]);
+    }
+
+    /// Steps a first ant that moves fine and then `agent`, checking nothing is kept.
+    fn fails_after_a_good_move(agent: Agent) -> Error {
+        let nodes = vec![DiscreteState::from_state(0); 16];
+        let mut system = system(&nodes);
+        let mut layer = ants(&[Agent::new(5, 0), agent]);
+        let before = layer.agents().to_vec();
+
+        let err = system.try_compute_agents(&mut layer).unwrap_err();
+
+        assert_eq!(layer.agents(), before);
+        assert_eq!(system.space_state(), nodes);
+        assert_eq!(system.generation(), 0);
+
+        err
+    }
+
+    #[test]
+    fn agents_off_the_space_or_the_rule_are_reported() {
+        assert!(matches!(
+            fails_after_a_good_move(Agent::new(16, 0)),
+            Error::AgentPosition {
+                agent: 1,
+                position: 16,
+                nodes: 16
+            }
+        ));
+
+        let confused = Agent {
+            state: 1,
+            ..Agent::new(3, 0)
+        };
+
+        assert!(matches!(
+            fails_after_a_good_move(confused),
+            Error::AgentState {
+                agent: 1,
+                state: 1,
+                states: 1
+            }
+        ));
+    }
+
+    #[test]
+    fn missing_headings_are_reported() {
+        let nodes = vec![DiscreteState::from_state(0); 16];
+        let mut system = system(&nodes);
+
+        // a compass heading past the end of the neighbor lists
+        let mut layer =
+            AgentLayer::new(Turmite::langtons_ant(), vec![0, 4, 2, 1]).with_agent(Agent::new(5, 0));
+
+        assert!(matches!(
+            system.try_compute_agents(&mut layer),
+            Err(Error::Neighborhood {
+                node: 5,
+                neighbors: 4
+            })
+        ));
+        assert_eq!(layer.agents(), [Agent::new(5, 0)]);
+        assert_eq!(system.space_state(), nodes);
+    }
+}
@@ -0,0 +1,238 @@
+use std::{fmt::Debug, hash::Hash};
+
+use crate::{
+    dynamics::local::LocalDynamic,
+    error::Error,
+    spaces::{
+        lattice::LatticeComputable,
+        local::{LocalHyperGraph, Stateable},
+    },
+    system::dynamical_system::DynamicalSystem,
+    third::wgpu::GpuDevice,
+};
+
+/// Which path executed a step.
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+pub enum BackendKind {
+    CpuSerial,
+    CpuRayon,
+    SoftwareGpu,
+    Gpu,
+}
+
+impl BackendKind {
+    pub fn is_gpu(&self) -> bool {
+        matches!(self, BackendKind::SoftwareGpu | BackendKind::Gpu)
+    }
+}
+
+/// A way of running one synchronous step of a system.
+pub trait Backend<S, D, N, E>
+where
+    S: LocalHyperGraph<N, E> + Debug + Clone,
+    D: LocalDynamic<N, E> + Debug + Clone,
+    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
+{
+    fn kind(&self) -> BackendKind;
+    fn step(&self, system: &mut DynamicalSystem<S, D, N, E>) -> Result<(), Error>;
+
+    /// Whether `step` can run `system`. By default, whether its dynamic supports the backend.
+    fn supports(&self, system: &DynamicalSystem<S, D, N, E>) -> bool {
+        system.dynamic().supports(self.kind())
+    }
+}
+
+/// Updates the nodes one after the other on the calling thread.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CpuSerial;
+
+/// Updates the nodes in parallel with rayon, as `compute_sync` does.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CpuRayon;
+
+/// Runs the WGSL kernel of the system on `device`. Only systems implementing `LatticeComputable`
+/// can use it, and only when their space is a 2D lattice linked the same way at every node.
+pub struct Gpu<'a> {
+    device: &'a GpuDevice,
+}
+
+impl<'a> Gpu<'a> {
+    pub fn new(device: &'a GpuDevice) -> Self {
+        Self { device }
+    }
+}
+
+impl<S, D, N, E> Backend<S, D, N, E> for CpuSerial
+where
+    S: LocalHyperGraph<N, E> + Debug + Clone,
+    D: LocalDynamic<N, E> + Debug + Clone,
+    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
+{
+    fn kind(&self) -> BackendKind {
+        BackendKind::CpuSerial
+    }
+
+    fn step(&self, system: &mut DynamicalSystem<S, D, N, E>) -> Result<(), Error> {
+        system.try_compute_sync_serial()
+    }
+}
+
+impl<S, D, N, E> Backend<S, D, N, E> for CpuRayon
+where
+    S: LocalHyperGraph<N, E> + Debug + Clone,
+    D: LocalDynamic<N, E> + Debug + Clone,
+    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
+{
+    fn kind(&self) -> BackendKind {
+        BackendKind::CpuRayon
+    }
+
+    fn step(&self, system: &mut DynamicalSystem<S, D, N, E>) -> Result<(), Error> {
+        system.try_compute_sync()
+    }
+}
+
+impl<S, D, N, E> Backend<S, D, N, E> for Gpu<'_>
+where
+    S: LocalHyperGraph<N, E> + Debug + Clone,
+    D: LocalDynamic<N, E> + Debug + Clone,
+    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
+    DynamicalSystem<S, D, N, E>: LatticeComputable<N, E>,
+{
+    fn kind(&self) -> BackendKind {
+        self.device.backend()
+    }
+
+    fn step(&self, system: &mut DynamicalSystem<S, D, N, E>) -> Result<(), Error> {
+        system.try_compute_sync_gpu(self.device)
+    }
+
+    fn supports(&self, system: &DynamicalSystem<S, D, N, E>) -> bool {
+        system.dynamic().supports(self.kind())
+            && system.shape().len() == 2
+            && system.observation_neighbors().is_ok()
+    }
+}
+
+impl<S, D, N, E> DynamicalSystem<S, D, N, E>
+where
+    S: LocalHyperGraph<N, E> + Debug + Clone,
+    D: LocalDynamic<N, E> + Debug + Clone,
+    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
+{
+    /// One synchronous step on `backend`, refused when it does not support the system. All
+    /// supported backends produce the same generations.
+    pub fn step(&mut self, backend: &dyn Backend<S, D, N, E>) -> Result<(), Error> {
+        if !backend.supports(self) {
+            return Err(Error::UnsupportedBackend {
+                backend: backend.kind(),
+            });
+        }
+
+        backend.step(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        dynamics::implementations::life::LifeLike,
+        spaces::{
+            implementations::basic::{DiscreteState, HyperGraphHeap},
+            local::HyperEdge,
+        },
+        third::{
+            codegen::{GridKernel, WgslRule},
+            wgpu::{select_device, ComputeDevice},
+        },
+    };
+
+    /// Keeps every cell as is on the CPU, with a kernel that does not compile.
+    #[derive(Clone, Debug)]
+    struct BrokenKernel;
+
+    impl LocalDynamic<DiscreteState, ()> for BrokenKernel {
+        fn states(&self) -> u32 {
+            2
+        }
+
+        fn update(
+            &self,
+            node: &DiscreteState,
+            _nodes: &[DiscreteState],
+            _edges: Vec<&HyperEdge<()>>,
+        ) -> DiscreteState {
+            node.clone()
+        }
+
+        fn supports(&self, _backend: BackendKind) -> bool {
+            true
+        }
+    }
+
+    impl GridKernel for BrokenKernel {
+        fn kernel_rule(&self, _offsets: &[(i32, i32)]) -> (u32, WgslRule) {
+            (2, WgslRule::Totalistic { table: vec![0; 10] })
+        }
+
+        fn kernel_wgsl(&self, _offsets: &[(i32, i32)]) -> Result<String, Error> {
+            Ok("@compute fn main( {".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn failing_gpu_steps_fall_back_to_the_cpu() {
+        let device = select_device().await;
+
+        let ComputeDevice::Gpu(gpu) = &device else {
+            eprintln!("no GPU adapter, skipping");
+            return;
+        };
+
+        let nodes = (0..16)
+            .map(|i| DiscreteState::from_state(i % 2))
+            .collect::<Vec<_>>();
+        let space = HyperGraphHeap::new_grid(&nodes, 4, 4, ());
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(BrokenKernel));
+
+        assert!(matches!(system.step(&Gpu::new(gpu)), Err(Error::Gpu(_))));
+        assert_eq!(system.generation(), 0);
+
+        system.compute_sync_on(&device).await.unwrap();
+
+        assert_eq!(system.generation(), 1);
+        assert_eq!(system.last_backend(), Some(BackendKind::CpuRayon));
+        assert_eq!(system.space_state(), nodes);
+    }
+
+    #[tokio::test]
+    async fn gpus_refuse_spaces_that_are_not_grids() {
+        let ComputeDevice::Gpu(gpu) = select_device().await else {
+            eprintln!("no GPU adapter, skipping");
+            return;
+        };
+
+        let nodes = vec![DiscreteState::from_state(1); 16];
+        let edges = (0..16)
+            .map(|i| (i, [1, 5, 7].map(|d| (vec![(i + d) % 16], ())).to_vec()))
+            .collect::<HashMap<usize, HyperEdge<()>>>();
+        let space = HyperGraphHeap::from_nodes_and_edges(nodes, edges, &(4, 4));
+
+        let mut system =
+            DynamicalSystem::new(Box::new(space), Box::new(LifeLike::new(&[3], &[2, 3])));
+
+        assert!(matches!(
+            system.step(&Gpu::new(&gpu)),
+            Err(Error::UnsupportedBackend { .. })
+        ));
+        assert!(system.step(&CpuRayon).is_ok());
+    }
+}
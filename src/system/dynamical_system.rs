@@ -1,14 +1,34 @@
-use std::{fmt::Debug, hash::Hash, ops::Deref};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
 
-use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
 use crate::{
-    dynamics::local::LocalDynamic,
+    dynamics::{
+        context::{NodeRng, UpdateContext},
+        local::LocalDynamic,
+    },
+    error::Error,
     spaces::{
+        implementations::grid::grid_layout,
         lattice::LatticeComputable,
         local::{LocalHyperGraph, Stateable},
     },
-    third::wgpu::{self, accumulation, GpuDevice, Image},
+    system::{
+        agents::{AgentLayer, AgentRule},
+        backend::BackendKind,
+        history::History,
+        observers::{Observer, Observers},
+    },
+    third::{
+        session::GpuSession,
+        wgpu::{self, accumulation, ComputeDevice, GpuDevice, Image, Real},
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -22,6 +42,15 @@ where
     _id: String,
     space: Box<S>,
     dynamic: Box<D>,
+    seed: u64,
+    generation: u64,
+    // generation before the current one, only kept for second-order dynamics
+    previous: Option<Vec<N>>,
+    // independent sets used by `compute_checkerboard`, built on first use
+    color_classes: Vec<Vec<usize>>,
+    observers: Observers<N>,
+    history: Option<History<N>>,
+    last_backend: Option<BackendKind>,
     phantom: std::marker::PhantomData<(N, E)>,
 }
 
@@ -37,6 +66,13 @@ where
             _id: "DynamicalSystem".to_string(),
             space,
             dynamic,
+            seed: 0,
+            generation: 0,
+            previous: None,
+            color_classes: Vec::new(),
+            observers: Observers::new(),
+            history: None,
+            last_backend: None,
             phantom: std::marker::PhantomData,
         }
     }
@@ -70,26 +106,442 @@ where
     }
 
     pub fn set_space(&mut self, space: Box<S>) {
-        self.space = space
+        self.space = space;
+        self.previous = None;
+        self.color_classes.clear();
+
+        if let Some(history) = self.history.as_mut() {
+            history.clear();
+        }
     }
 
+    /// Edits the nodes in place. The edit is not a step: it is neither observed nor recorded in
+    /// the history, so rewinding across it only restores the nodes later steps changed.
     pub fn update_space(&mut self, mutator: impl FnMut(&mut Vec<N>)) {
         self.space.update_nodes_mut(mutator);
     }
 
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Seeds the per-node random streams handed to the dynamic on every step.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn set_generation(&mut self, generation: u64) {
+        self.generation = generation
+    }
+
+    /// Backend that executed the latest step, `None` before the first one.
+    pub fn last_backend(&self) -> Option<BackendKind> {
+        self.last_backend
+    }
+
+    pub fn previous_state(&self) -> Option<&Vec<N>> {
+        self.previous.as_ref()
+    }
+
+    /// Sets the generation a second-order dynamic sees before the current one. Without it the
+    /// first step reads every previous state as 0.
+    pub fn set_previous_state(&mut self, previous: Vec<N>) {
+        self.previous = Some(previous)
+    }
+
+    /// Registers `observer` to run after every step. The returned handle gives access to what it
+    /// collected. Observers stay with this system: clones of it start without any.
+    pub fn observe<O: Observer<N> + 'static>(&mut self, observer: O) -> Arc<Mutex<O>> {
+        self.observe_every(1, observer)
+    }
+
+    /// Registers `observer` to run after the steps reaching a generation divisible by `every`.
+    pub fn observe_every<O: Observer<N> + 'static>(
+        &mut self,
+        every: u64,
+        observer: O,
+    ) -> Arc<Mutex<O>> {
+        let observer = Arc::new(Mutex::new(observer));
+
+        self.observers.register(every, observer.clone());
+
+        observer
+    }
+
+    pub fn clear_observers(&mut self) {
+        self.observers.clear();
+    }
+
+    /// Keeps the last `capacity` steps, as the nodes each one changed, so they can be rewound.
+    pub fn enable_history(&mut self, capacity: usize) {
+        self.history = Some(History::new(capacity));
+    }
+
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Number of steps that can currently be rewound. A second-order dynamic keeps the oldest
+    /// recorded step, which is what tells the generation before the one it would rewind to.
+    pub fn history_len(&self) -> usize {
+        self.history
+            .as_ref()
+            .map_or(0, |h| h.len().saturating_sub(self.kept_steps()))
+    }
+
+    fn kept_steps(&self) -> usize {
+        usize::from(self.dynamic.is_second_order())
+    }
+
+    /// Undoes up to `steps` recorded steps and returns how many were undone.
+    pub fn rewind(&mut self, steps: usize) -> usize {
+        let steps = steps.min(self.history_len());
+
+        let Some(history) = self.history.as_mut() else {
+            return 0;
+        };
+
+        let mut nodes = self.space.nodes().clone();
+        let mut undone = 0;
+
+        while undone < steps && history.undo(&mut nodes) {
+            undone += 1;
+        }
+
+        if undone > 0 {
+            if self.dynamic.is_second_order() {
+                self.previous = history.peek(&nodes);
+            }
+
+            self.space.update_nodes(nodes);
+            self.generation -= undone as u64;
+        }
+
+        undone
+    }
+
+    /// Rewinds to `generation` if the history reaches back that far.
+    pub fn rewind_to(&mut self, generation: u64) -> bool {
+        if generation > self.generation || self.generation - generation > self.history_len() as u64
+        {
+            return false;
+        }
+
+        self.rewind((self.generation - generation) as usize);
+
+        true
+    }
+
     pub fn compute_sync(&mut self) {
-        let mut new_nodes = self.space.nodes().clone();
+        if let Err(err) = self.try_compute_sync() {
+            panic!("{err}");
+        }
+    }
+
+    /// Like `compute_sync`, but reports a node without a neighbor entry instead of panicking. The
+    /// system is left untouched on error.
+    pub fn try_compute_sync(&mut self) -> Result<(), Error> {
+        let new_nodes = self.next_nodes()?;
+
+        self.commit(new_nodes, BackendKind::CpuRayon);
+
+        Ok(())
+    }
 
-        new_nodes.par_iter_mut().enumerate().for_each(|(i, node)| {
-            let neighbors = self.space.node_neighbors().get(&i).unwrap().to_owned();
+    /// `try_compute_sync` on the calling thread only.
+    pub(crate) fn try_compute_sync_serial(&mut self) -> Result<(), Error> {
+        self.check_layout()?;
 
-            let neighbor_nodes = neighbors
-                .iter()
-                .map(|i| self.space.nodes()[*i].clone())
-                .collect::<Vec<N>>();
+        let new_nodes = (0..self.space.nodes().len())
+            .map(|i| self.next_node(i))
+            .collect::<Result<Vec<N>, Error>>()?;
 
-            *node = self.dynamic.update(node, &neighbor_nodes, vec![]);
-        });
+        self.commit(new_nodes, BackendKind::CpuSerial);
+
+        Ok(())
+    }
+
+    /// Steps a second-order system back one generation: the previous and current generations
+    /// trade places, a forward step runs, and they trade back.
+    pub fn compute_sync_backward(&mut self) {
+        if let Err(err) = self.try_compute_sync_backward() {
+            panic!("{err}");
+        }
+    }
+
+    /// Like `compute_sync_backward`, but reports a missing previous generation or neighbor entry,
+    /// or a step back from generation 0, instead of panicking. The system is left untouched on
+    /// error.
+    pub fn try_compute_sync_backward(&mut self) -> Result<(), Error> {
+        let previous = self.previous.take().ok_or(Error::MissingPrevious)?;
+
+        let Some(generation) = self.generation.checked_sub(1) else {
+            self.previous = Some(previous);
+
+            return Err(Error::FirstGeneration);
+        };
+
+        let current = self.space.nodes().clone();
+
+        self.space.update_nodes(previous);
+        self.previous = Some(current);
+
+        let earlier = match self.next_nodes() {
+            Ok(earlier) => earlier,
+            Err(err) => {
+                let previous = self.space.nodes().clone();
+
+                self.space.update_nodes(self.previous.take().unwrap());
+                self.previous = Some(previous);
+
+                return Err(err);
+            }
+        };
+
+        self.previous = Some(earlier);
+        self.generation = generation;
+
+        // the recorded steps lead to generations this one has now been stepped away from
+        if let Some(history) = self.history.as_mut() {
+            history.clear();
+        }
+
+        Ok(())
+    }
+
+    fn next_nodes(&self) -> Result<Vec<N>, Error> {
+        self.check_layout()?;
+
+        (0..self.space.nodes().len())
+            .into_par_iter()
+            .map(|i| self.next_node(i))
+            .collect()
+    }
+
+    fn next_node(&self, i: usize) -> Result<N, Error> {
+        let neighbors = self.neighbors_of(i)?;
+
+        let neighbor_nodes = neighbors
+            .iter()
+            .map(|i| self.space.nodes()[*i].clone())
+            .collect::<Vec<N>>();
+
+        let mut context = UpdateContext::new(self.seed, i, self.generation);
+        context.previous = self.previous_of(i);
+
+        Ok(self.dynamic.update_with_context(
+            &self.space.nodes()[i],
+            &neighbor_nodes,
+            vec![],
+            &mut context,
+        ))
+    }
+
+    /// Updates the nodes one independent set at a time, each set seeing the ones before it. On a
+    /// von Neumann grid with even sides the sets are the two colors of a checkerboard.
+    pub fn compute_checkerboard(&mut self) {
+        if let Err(err) = self.try_compute_checkerboard() {
+            panic!("{err}");
+        }
+    }
+
+    /// Like `compute_checkerboard`, but reports a node the dynamic cannot update instead of
+    /// panicking. The system is left untouched on error.
+    pub fn try_compute_checkerboard(&mut self) -> Result<(), Error> {
+        self.check_layout()?;
+
+        if self.color_classes.is_empty() {
+            self.color_classes =
+                color_classes(self.space.node_neighbors(), self.space.nodes().len());
+        }
+
+        let mut nodes = self.space.nodes().clone();
+        let (seed, generation) = (self.seed, self.generation);
+
+        for class in &self.color_classes {
+            let updates = class
+                .par_iter()
+                .map(|&i| {
+                    let neighbor_nodes = self.neighbor_nodes(&nodes, i)?;
+                    let mut context = UpdateContext::new(seed, i, generation);
+                    context.previous = self.previous_of(i);
+
+                    let node = self.dynamic.update_with_context(
+                        &nodes[i],
+                        &neighbor_nodes,
+                        vec![],
+                        &mut context,
+                    );
+
+                    Ok((i, node))
+                })
+                .collect::<Result<Vec<(usize, N)>, Error>>()?;
+
+            for (i, node) in updates {
+                nodes[i] = node;
+            }
+        }
+
+        self.commit(nodes, BackendKind::CpuRayon);
+
+        Ok(())
+    }
+
+    /// One sweep of as many single-node updates as there are nodes, each at a uniformly chosen
+    /// node and applied immediately.
+    pub fn compute_random_sequential(&mut self) {
+        if let Err(err) = self.try_compute_random_sequential() {
+            panic!("{err}");
+        }
+    }
+
+    /// Like `compute_random_sequential`, but reports a node the dynamic cannot update instead of
+    /// panicking. The system is left untouched on error.
+    pub fn try_compute_random_sequential(&mut self) -> Result<(), Error> {
+        self.check_layout()?;
+
+        let mut nodes = self.space.nodes().clone();
+        let len = nodes.len();
+        let generation = self.generation;
+
+        // node indices stop at `len - 1`, so `len` names a stream no node uses
+        let mut sweep = NodeRng::for_node(self.seed, len, generation);
+
+        for _ in 0..len {
+            let i = sweep.gen_range(len as u32) as usize;
+            let neighbor_nodes = self.neighbor_nodes(&nodes, i)?;
+
+            let mut context = UpdateContext {
+                index: i,
+                generation,
+                rng: NodeRng::new(sweep.next_u64()),
+                previous: self.previous_of(i),
+            };
+
+            nodes[i] =
+                self.dynamic
+                    .update_with_context(&nodes[i], &neighbor_nodes, vec![], &mut context);
+        }
+
+        self.commit(nodes, BackendKind::CpuSerial);
+
+        Ok(())
+    }
+
+    /// One move of every agent in `layer`, which read and write the nodes they stand on in
+    /// place of the dynamic.
+    pub fn compute_agents<R: AgentRule>(&mut self, layer: &mut AgentLayer<R>) {
+        if let Err(err) = self.try_compute_agents(layer) {
+            panic!("{err}");
+        }
+    }
+
+    /// Like `compute_agents`, but reports the errors of `AgentLayer::try_step` instead of
+    /// panicking. The system and the layer are left untouched on error.
+    pub fn try_compute_agents<R: AgentRule>(
+        &mut self,
+        layer: &mut AgentLayer<R>,
+    ) -> Result<(), Error> {
+        let mut states = self
+            .space
+            .nodes()
+            .iter()
+            .map(|n| n.state())
+            .collect::<Vec<u32>>();
+
+        layer.try_step(&mut states, self.space.node_neighbors())?;
+
+        let nodes = self
+            .space
+            .nodes()
+            .iter()
+            .zip(states)
+            .map(|(node, state)| {
+                let mut node = node.clone();
+                node.set_state(state);
+                node
+            })
+            .collect();
+
+        self.commit(nodes, BackendKind::CpuSerial);
+
+        Ok(())
+    }
+
+    /// Checks the space is a grid the dynamic accepts when it reads its neighbors by their place
+    /// in one.
+    fn check_layout(&self) -> Result<(), Error> {
+        let nodes = self.space.nodes().len();
+
+        if !self.dynamic.reads_grid_layout() || nodes == 0 {
+            return Ok(());
+        }
+
+        match grid_layout(self.space.node_neighbors(), nodes) {
+            Ok((width, height)) if !self.dynamic.accepts_grid(width, height) => {
+                Err(Error::GridSize { width, height })
+            }
+            Ok(_) => Ok(()),
+            Err(Error::NotAGrid { node }) => Err(match self.space.node_neighbors().get(&node) {
+                Some(neighbors) => Error::Neighborhood {
+                    node,
+                    neighbors: neighbors.len(),
+                },
+                None => Error::MissingNeighbors { node },
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Neighbors of node `i`, provided the dynamic accepts that many.
+    fn neighbors_of(&self, i: usize) -> Result<&Vec<usize>, Error> {
+        let neighbors = self
+            .space
+            .node_neighbors()
+            .get(&i)
+            .ok_or(Error::MissingNeighbors { node: i })?;
+
+        if !self.dynamic.accepts(neighbors.len()) {
+            return Err(Error::Neighborhood {
+                node: i,
+                neighbors: neighbors.len(),
+            });
+        }
+
+        Ok(neighbors)
+    }
+
+    fn neighbor_nodes(&self, nodes: &[N], i: usize) -> Result<Vec<N>, Error> {
+        Ok(self
+            .neighbors_of(i)?
+            .iter()
+            .map(|j| nodes[*j].clone())
+            .collect())
+    }
+
+    fn previous_of(&self, i: usize) -> Option<u32> {
+        self.previous.as_ref().map(|previous| previous[i].state())
+    }
+
+    pub(crate) fn commit(&mut self, new_nodes: Vec<N>, backend: BackendKind) {
+        self.generation += 1;
+        self.last_backend = Some(backend);
+
+        self.observers
+            .notify(self.generation, &new_nodes, self.space.nodes());
+
+        if let Some(history) = self.history.as_mut() {
+            history.record(self.space.nodes(), &new_nodes);
+        }
+
+        if self.dynamic.is_second_order() {
+            self.previous = Some(self.space.nodes().clone());
+        }
 
         self.space.update_nodes(new_nodes);
     }
@@ -104,10 +556,83 @@ where
     Self: LatticeComputable<N, E>,
 {
     pub async fn compute_sync_wgpu(&mut self, device: &GpuDevice) {
+        if let Err(err) = self.try_compute_sync_wgpu(device).await {
+            panic!("{err}");
+        }
+    }
+
+    /// Steps on the GPU when `device` holds one and on the rayon CPU path otherwise, or when the
+    /// GPU step fails; `last_backend` tells which one ran.
+    pub async fn compute_sync_on(&mut self, device: &ComputeDevice) -> Result<(), Error> {
+        if let ComputeDevice::Gpu(device) = device {
+            if self.try_compute_sync_wgpu(device).await.is_ok() {
+                return Ok(());
+            }
+        }
+
+        self.try_compute_sync()
+    }
+
+    /// Uploads the current generation to `device` to step it there with `GpuSession::run`.
+    pub fn gpu_session<'a>(&self, device: &'a GpuDevice) -> Result<GpuSession<'a>, Error> {
+        let cells = self
+            .space
+            .nodes()
+            .iter()
+            .map(|x| x.state() as Real)
+            .collect::<Vec<Real>>();
+
+        GpuSession::new(device, self, &cells, self.generation)
+    }
+
+    /// Takes over the generation `session` reached. Observers due at that generation run once,
+    /// with the generation before it as the previous one, and second-order dynamics keep that
+    /// generation too. The generations run on the GPU in between are neither observed nor
+    /// recorded, so the history is cleared.
+    pub fn read_session(&mut self, session: &GpuSession) -> Result<(), Error> {
+        let to_nodes = |cells: Vec<Real>| {
+            cells
+                .into_iter()
+                .map(|cell| N::from_state(cell as u32))
+                .collect::<Vec<N>>()
+        };
+
+        let nodes = to_nodes(session.read()?);
+        let previous = session.read_previous()?.map(to_nodes);
+
+        self.observers.notify(
+            session.generation(),
+            &nodes,
+            previous.as_ref().unwrap_or(self.space.nodes()),
+        );
+
+        if self.dynamic.is_second_order() && previous.is_some() {
+            self.previous = previous;
+        }
+
+        self.space.update_nodes(nodes);
+        self.generation = session.generation();
+        self.last_backend = Some(session.backend());
+
+        if let Some(history) = self.history.as_mut() {
+            history.clear();
+        }
+
+        Ok(())
+    }
+
+    /// Like `compute_sync_wgpu`, but reports lattices that are not 2D instead of panicking.
+    pub async fn try_compute_sync_wgpu(&mut self, device: &GpuDevice) -> Result<(), Error> {
+        self.try_compute_sync_gpu(device)
+    }
+
+    pub(crate) fn try_compute_sync_gpu(&mut self, device: &GpuDevice) -> Result<(), Error> {
         let shape = self.shape();
 
         if shape.len() != 2 {
-            panic!("Only 2D lattices are supported for now");
+            return Err(Error::UnsupportedShape {
+                dimensions: shape.len(),
+            });
         }
 
         let (w, h) = (shape[0], shape[1]);
@@ -121,7 +646,7 @@ where
 
         let kernel = accumulation();
 
-        let output = wgpu::run(
+        let output = wgpu::run_blocking(
             device,
             &Image {
                 data: mem,
@@ -129,18 +654,181 @@ where
                 height: h as u32,
             },
             &kernel,
-            self.update_wgsl_code(),
+            self.update_wgsl_code()?,
             self.deref(),
-        )
-        .await;
+        )?;
+
+        let nodes = output
+            .data
+            .iter()
+            .map(|&cell| N::from_state(cell as u32))
+            .collect::<Vec<N>>();
+
+        self.commit(nodes, device.backend());
+
+        Ok(())
+    }
+}
+
+/// Greedy coloring in node order. Links count in both directions, so no class holds a node and
+/// one of its neighbors even when the neighbor lists are not symmetric.
+fn color_classes(neighbors: &HashMap<usize, Vec<usize>>, len: usize) -> Vec<Vec<usize>> {
+    let mut adjacent = vec![Vec::new(); len];
+
+    for (&i, links) in neighbors {
+        for &j in links.iter().filter(|&&j| j != i) {
+            adjacent[i].push(j);
+            adjacent[j].push(i);
+        }
+    }
+
+    let mut colors = vec![usize::MAX; len];
+    let mut classes: Vec<Vec<usize>> = Vec::new();
+
+    for i in 0..len {
+        let taken = adjacent[i]
+            .iter()
+            .map(|&j| colors[j])
+            .collect::<Vec<usize>>();
+
+        let color = (0..).find(|c| !taken.contains(c)).unwrap();
+
+        if color == classes.len() {
+            classes.push(Vec::new());
+        }
+
+        colors[i] = color;
+        classes[color].push(i);
+    }
+
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamics::{
+            combinators::Alternating,
+            implementations::{
+                life::LifeLike,
+                partitioned::{BlockTable, Partitioned},
+            },
+        },
+        spaces::implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::VON_NEUMANN_NEIGHBORHOOD,
+        },
+    };
+
+    #[test]
+    fn color_classes_are_independent_on_directed_graphs() {
+        // a directed cycle plus a chord only 3 knows about
+        let neighbors = HashMap::from([(0, vec![1]), (1, vec![2]), (2, vec![0]), (3, vec![0])]);
+
+        let classes = color_classes(&neighbors, 4);
+
+        for class in &classes {
+            for (&i, links) in &neighbors {
+                if class.contains(&i) {
+                    assert!(links.iter().all(|j| !class.contains(j)), "{classes:?}");
+                }
+            }
+        }
+
+        assert_eq!(classes.concat().len(), 4);
+    }
+
+    #[test]
+    fn every_update_order_reports_refused_neighborhoods() {
+        let nodes = vec![DiscreteState::from_state(1); 16];
+        let space =
+            HyperGraphHeap::new_grid_with_neighborhood(&nodes, 4, 4, &VON_NEUMANN_NEIGHBORHOOD, ());
+
+        // life accepts any neighborhood, the partitioned half of the combination does not
+        let dynamic = Alternating::new(
+            LifeLike::new(&[3], &[2, 3]),
+            Partitioned::new(BlockTable::critters(), 4),
+        );
+
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
+
+        assert!(matches!(
+            system.try_compute_sync(),
+            Err(Error::Neighborhood { neighbors: 4, .. })
+        ));
+        assert!(matches!(
+            system.try_compute_checkerboard(),
+            Err(Error::Neighborhood { neighbors: 4, .. })
+        ));
+        assert!(matches!(
+            system.try_compute_random_sequential(),
+            Err(Error::Neighborhood { neighbors: 4, .. })
+        ));
+
+        assert_eq!(system.generation(), 0);
+        assert_eq!(system.space_state(), nodes);
+    }
+
+    type Life =
+        DynamicalSystem<HyperGraphHeap<DiscreteState, (), (u32, u32)>, LifeLike, DiscreteState, ()>;
+
+    /// A glider stepped 5 times with the last 3 steps kept, and every generation it went through.
+    fn glider_system() -> (Life, Vec<Vec<DiscreteState>>) {
+        let mut nodes = vec![DiscreteState::from_state(0); 64];
+
+        for i in [1, 10, 16, 17, 18] {
+            nodes[i] = DiscreteState::from_state(1);
+        }
+
+        let space = HyperGraphHeap::new_grid(&nodes, 8, 8, ());
+        let mut system =
+            DynamicalSystem::new(Box::new(space), Box::new(LifeLike::new(&[3], &[2, 3])));
+        system.enable_history(3);
+
+        let mut trajectory = vec![system.space_state()];
+
+        for _ in 0..5 {
+            system.compute_sync();
+            trajectory.push(system.space_state());
+        }
+
+        (system, trajectory)
+    }
+
+    #[test]
+    fn rewinding_is_bounded_by_the_history() {
+        let (mut system, trajectory) = glider_system();
+
+        // 5 steps taken, the last 3 kept
+        assert_eq!(system.history_len(), 3);
+
+        assert_eq!(system.rewind(1), 1);
+        assert_eq!(system.generation(), 4);
+        assert_eq!(system.space_state(), trajectory[4]);
+
+        assert_eq!(system.rewind(10), 2);
+        assert_eq!(system.generation(), 2);
+        assert_eq!(system.space_state(), trajectory[2]);
+        assert_eq!(system.rewind(1), 0);
+
+        // stepping again records anew
+        system.compute_sync();
+        assert_eq!(system.space_state(), trajectory[3]);
+        assert_eq!(system.history_len(), 1);
+    }
 
-        let res_data_len = output.data.len();
-        let mut nodes = self.space.nodes().to_owned();
+    #[test]
+    fn rewinding_to_a_dropped_generation_leaves_the_system() {
+        let (mut system, trajectory) = glider_system();
 
-        nodes.iter_mut().enumerate().for_each(|(i, x)| {
-            *x = N::from_state(output.data[i % res_data_len] as u32); //LifeState( as u8);
-        });
+        assert!(!system.rewind_to(1));
+        assert!(!system.rewind_to(6));
+        assert_eq!(system.generation(), 5);
+        assert_eq!(system.space_state(), trajectory[5]);
 
-        self.space.update_nodes(nodes.clone());
+        assert!(system.rewind_to(2));
+        assert_eq!(system.generation(), 2);
+        assert_eq!(system.space_state(), trajectory[2]);
     }
 }
@@ -0,0 +1,397 @@
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{
+    dynamics::{
+        context::NodeRng,
+        implementations::{
+            neighborhood_table::NeighborhoodTable,
+            totalistic::{OuterTotalisticTable, TotalisticTable},
+        },
+        statistics::TransitionRule,
+    },
+    spaces::{
+        implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::one_dimensional_neighborhood,
+        },
+        local::Stateable,
+    },
+    system::dynamical_system::DynamicalSystem,
+};
+
+/// A rule the search can vary.
+pub trait Genome: Clone + Send + Sync {
+    /// Redraws each gene with probability `rate`.
+    fn mutate(&mut self, rate: f64, rng: &mut NodeRng);
+    fn crossover(&self, other: &Self, rng: &mut NodeRng) -> Self;
+}
+
+fn mutate_genes(genes: &mut [u32], states: u32, rate: f64, rng: &mut NodeRng) {
+    for gene in genes.iter_mut() {
+        if rng.gen_bool(rate) {
+            *gene = (*gene + 1 + rng.gen_range(states - 1)) % states;
+        }
+    }
+}
+
+/// Single-point crossover.
+fn cross_genes(a: &[u32], b: &[u32], rng: &mut NodeRng) -> Vec<u32> {
+    let point = rng.gen_range(a.len() as u32 + 1) as usize;
+
+    a[..point].iter().chain(&b[point..]).copied().collect()
+}
+
+impl Genome for NeighborhoodTable {
+    fn mutate(&mut self, rate: f64, rng: &mut NodeRng) {
+        let states = self.alphabet();
+        let mut genes = self.table().to_vec();
+
+        mutate_genes(&mut genes, states, rate, rng);
+
+        *self = Self::new(states, self.neighbors(), genes);
+    }
+
+    fn crossover(&self, other: &Self, rng: &mut NodeRng) -> Self {
+        let states = self.alphabet();
+
+        Self::new(
+            states,
+            self.neighbors(),
+            cross_genes(self.table(), other.table(), rng),
+        )
+    }
+}
+
+impl Genome for TotalisticTable {
+    fn mutate(&mut self, rate: f64, rng: &mut NodeRng) {
+        let states = self.alphabet();
+        let mut genes = self.table().to_vec();
+
+        mutate_genes(&mut genes, states, rate, rng);
+
+        *self = Self::new(states, genes);
+    }
+
+    fn crossover(&self, other: &Self, rng: &mut NodeRng) -> Self {
+        let states = self.alphabet();
+
+        Self::new(states, cross_genes(self.table(), other.table(), rng))
+    }
+}
+
+impl Genome for OuterTotalisticTable {
+    fn mutate(&mut self, rate: f64, rng: &mut NodeRng) {
+        let states = self.alphabet();
+        let mut rows = self.rows();
+
+        for row in rows.iter_mut() {
+            mutate_genes(row, states, rate, rng);
+        }
+
+        *self = Self::new(states, rows);
+    }
+
+    fn crossover(&self, other: &Self, rng: &mut NodeRng) -> Self {
+        let states = self.alphabet();
+        let width = self.rows()[0].len();
+
+        let genes = cross_genes(&self.rows().concat(), &other.rows().concat(), rng);
+
+        Self::new(
+            states,
+            genes.chunks(width).map(|row| row.to_vec()).collect(),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GenerationReport<G> {
+    pub generation: u64,
+    /// Seed every individual of the generation was evaluated with; passing it back to the
+    /// fitness function reproduces `best_fitness`.
+    pub evaluation_seed: u64,
+    pub best: G,
+    pub best_fitness: f64,
+    pub mean_fitness: f64,
+}
+
+/// Generational genetic algorithm: the fittest `elite` rules survive unchanged and the rest of
+/// the population is refilled with mutated crossovers of tournament winners.
+///
+/// The fitness function receives a rule and a seed for whatever randomness the evaluation needs,
+/// such as initial conditions; all individuals of a generation share the seed so that they face
+/// the same trials. Everything derives from the search seed, so a run can be replayed exactly.
+pub struct Evolution<G, F>
+where
+    G: Genome,
+    F: Fn(&G, u64) -> f64 + Sync,
+{
+    population: Vec<G>,
+    fitness: F,
+    seed: u64,
+    generation: u64,
+    elite: usize,
+    mutation_rate: f64,
+    tournament: usize,
+    reports: Vec<GenerationReport<G>>,
+}
+
+impl<G, F> Evolution<G, F>
+where
+    G: Genome,
+    F: Fn(&G, u64) -> f64 + Sync,
+{
+    pub fn new(population: Vec<G>, fitness: F, seed: u64) -> Self {
+        assert!(!population.is_empty(), "empty population");
+
+        Self {
+            elite: population.len() / 5,
+            population,
+            fitness,
+            seed,
+            generation: 0,
+            mutation_rate: 0.02,
+            tournament: 2,
+            reports: Vec::new(),
+        }
+    }
+
+    pub fn with_elite(mut self, elite: usize) -> Self {
+        self.elite = elite.min(self.population.len());
+        self
+    }
+
+    pub fn with_mutation_rate(mut self, rate: f64) -> Self {
+        self.mutation_rate = rate;
+        self
+    }
+
+    pub fn with_tournament(mut self, size: usize) -> Self {
+        self.tournament = size.max(1);
+        self
+    }
+
+    pub fn population(&self) -> &[G] {
+        &self.population
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn reports(&self) -> &[GenerationReport<G>] {
+        &self.reports
+    }
+
+    /// Best rule seen over the whole run.
+    pub fn best(&self) -> Option<&GenerationReport<G>> {
+        self.reports
+            .iter()
+            .max_by(|a, b| a.best_fitness.total_cmp(&b.best_fitness))
+    }
+
+    /// Evaluates the current population in parallel, records its report and breeds the next one.
+    pub fn step(&mut self) -> &GenerationReport<G> {
+        let evaluation_seed = NodeRng::for_node(self.seed, 0, self.generation).next_u64();
+
+        let scores = self
+            .population
+            .par_iter()
+            .map(|genome| (self.fitness)(genome, evaluation_seed))
+            .collect::<Vec<f64>>();
+
+        let mut ranking = (0..scores.len()).collect::<Vec<usize>>();
+        ranking.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+
+        self.reports.push(GenerationReport {
+            generation: self.generation,
+            evaluation_seed,
+            best: self.population[ranking[0]].clone(),
+            best_fitness: scores[ranking[0]],
+            mean_fitness: scores.iter().sum::<f64>() / scores.len() as f64,
+        });
+
+        let mut rng = NodeRng::for_node(self.seed, 1, self.generation);
+
+        let mut next = ranking[..self.elite]
+            .iter()
+            .map(|&i| self.population[i].clone())
+            .collect::<Vec<G>>();
+
+        while next.len() < self.population.len() {
+            let a = &self.population[self.select(&scores, &mut rng)];
+            let b = &self.population[self.select(&scores, &mut rng)];
+
+            let mut child = a.crossover(b, &mut rng);
+            child.mutate(self.mutation_rate, &mut rng);
+
+            next.push(child);
+        }
+
+        self.population = next;
+        self.generation += 1;
+
+        self.reports.last().unwrap()
+    }
+
+    pub fn run(&mut self, generations: usize) -> &[GenerationReport<G>] {
+        for _ in 0..generations {
+            self.step();
+        }
+
+        &self.reports
+    }
+
+    fn select(&self, scores: &[f64], rng: &mut NodeRng) -> usize {
+        (0..self.tournament)
+            .map(|_| rng.gen_range(scores.len() as u32) as usize)
+            .max_by(|&a, &b| scores[a].total_cmp(&scores[b]))
+            .unwrap()
+    }
+}
+
+/// Fitness for the density classification task: the share of `trials` random rings of `width`
+/// cells that `rule` drives, within `steps` steps, to all ones when ones were the majority and to
+/// all zeros otherwise. Initial densities are uniform in `[0, 1]`; use an odd width so that
+/// there are no ties. The rule must be binary, its neighbors being the cells on either side.
+pub fn density_classification(
+    rule: &NeighborhoodTable,
+    width: u32,
+    trials: usize,
+    steps: usize,
+    seed: u64,
+) -> f64 {
+    let neighborhood = one_dimensional_neighborhood(rule.neighbors() as i32 / 2);
+
+    let solved = (0..trials)
+        .filter(|&trial| {
+            let mut rng = NodeRng::for_node(seed, trial, 0);
+            let density = rng.next_f64();
+
+            let nodes = (0..width)
+                .map(|_| DiscreteState::from_state(rng.gen_bool(density) as u32))
+                .collect::<Vec<DiscreteState>>();
+
+            let ones = nodes.iter().filter(|n| n.state() == 1).count();
+            let majority = (2 * ones > width as usize) as u32;
+
+            let space =
+                HyperGraphHeap::new_grid_with_neighborhood(&nodes, width, 1, &neighborhood, ());
+            let mut system = DynamicalSystem::new(Box::new(space), Box::new(rule.clone()));
+
+            for _ in 0..steps {
+                system.compute_sync();
+            }
+
+            system.space_state().iter().all(|n| n.state() == majority)
+        })
+        .count();
+
+    solved as f64 / trials as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ones(rule: &NeighborhoodTable) -> f64 {
+        rule.table().iter().sum::<u32>() as f64
+    }
+
+    fn population(seed: u64) -> Vec<NeighborhoodTable> {
+        let mut rng = NodeRng::new(seed);
+
+        (0..12)
+            .map(|_| NeighborhoodTable::random(2, 2, &mut rng))
+            .collect()
+    }
+
+    fn summary(
+        evolution: &Evolution<NeighborhoodTable, impl Fn(&NeighborhoodTable, u64) -> f64 + Sync>,
+    ) -> Vec<(u64, u64, NeighborhoodTable, f64, f64)> {
+        evolution
+            .reports()
+            .iter()
+            .map(|r| {
+                (
+                    r.generation,
+                    r.evaluation_seed,
+                    r.best.clone(),
+                    r.best_fitness,
+                    r.mean_fitness,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn runs_replay_from_their_seed() {
+        // the evaluation seed is part of the score, so it has to be replayed too
+        let fitness = |rule: &NeighborhoodTable, seed: u64| ones(rule) + (seed % 1000) as f64;
+
+        let mut first = Evolution::new(population(1), fitness, 5);
+        let mut second = Evolution::new(population(1), fitness, 5);
+        let mut other = Evolution::new(population(1), fitness, 6);
+
+        first.run(10);
+        second.run(10);
+        other.run(10);
+
+        assert_eq!(summary(&first), summary(&second));
+        assert_eq!(first.population(), second.population());
+
+        assert_ne!(summary(&first), summary(&other));
+    }
+
+    #[test]
+    fn elites_carry_over_unchanged() {
+        let fitness = |rule: &NeighborhoodTable, _: u64| ones(rule);
+        let mut evolution = Evolution::new(population(2), fitness, 7)
+            .with_elite(3)
+            .with_mutation_rate(0.5);
+
+        for _ in 0..5 {
+            let mut ranked = evolution.population().to_vec();
+            ranked.sort_by(|a, b| ones(b).total_cmp(&ones(a)));
+
+            evolution.step();
+
+            assert_eq!(evolution.population()[..3], ranked[..3]);
+        }
+    }
+
+    #[test]
+    fn density_classification_ranks_known_rules() {
+        let majority = NeighborhoodTable::new(
+            2,
+            6,
+            (0..128u32).map(|i| (i.count_ones() > 3) as u32).collect(),
+        );
+
+        // Gacs–Kurdyumov–Levin: a 0 polls itself and the cells 1 and 3 to its left, a 1 those to
+        // its right; the center is the top bit and the cells to the left follow it
+        let gkl = NeighborhoodTable::new(
+            2,
+            6,
+            (0..128u32)
+                .map(|i| {
+                    let cell = |bit: u32| (i >> bit) & 1;
+                    let polled = match cell(6) {
+                        0 => cell(3) + cell(5),
+                        _ => 1 + cell(2) + cell(0),
+                    };
+
+                    (polled >= 2) as u32
+                })
+                .collect(),
+        );
+
+        let constant = NeighborhoodTable::new(2, 6, vec![0; 128]);
+
+        let score = |rule: &NeighborhoodTable| density_classification(rule, 49, 100, 98, 1);
+        let (gkl, majority, constant) = (score(&gkl), score(&majority), score(&constant));
+
+        assert!(gkl > majority, "{gkl} {majority}");
+        assert!(majority > constant, "{majority} {constant}");
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+
+/// Bounded record of the last steps of a system, each kept as the nodes it changed together with
+/// their values before the step. The record grows with the steps, so a large capacity costs
+/// nothing until it is used, and the oldest step is dropped once it is full.
+#[derive(Clone, Debug)]
+pub(crate) struct History<N> {
+    capacity: usize,
+    steps: VecDeque<Vec<(usize, N)>>,
+}
+
+impl<N: Clone + PartialEq> History<N> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            steps: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.steps.clear();
+    }
+
+    pub(crate) fn record(&mut self, before: &[N], after: &[N]) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.steps.len() == self.capacity {
+            self.steps.pop_front();
+        }
+
+        let changes = before
+            .iter()
+            .zip(after)
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(i, (old, _))| (i, old.clone()))
+            .collect();
+
+        self.steps.push_back(changes);
+    }
+
+    /// Turns `nodes` back into the generation before the last recorded step, forgetting it.
+    pub(crate) fn undo(&mut self, nodes: &mut [N]) -> bool {
+        match self.steps.pop_back() {
+            Some(changes) => {
+                Self::apply(&changes, nodes);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// What `undo` would turn `nodes` into, leaving the history as is.
+    pub(crate) fn peek(&self, nodes: &[N]) -> Option<Vec<N>> {
+        let mut earlier = nodes.to_vec();
+
+        Self::apply(self.steps.back()?, &mut earlier);
+
+        Some(earlier)
+    }
+
+    fn apply(changes: &[(usize, N)], nodes: &mut [N]) {
+        for (i, node) in changes {
+            nodes[*i] = node.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_histories_drop_the_oldest_step() {
+        let mut history = History::new(2);
+        let mut nodes = vec![0, 0, 0];
+
+        for generation in 1..=3 {
+            let next = vec![generation; 3];
+            history.record(&nodes, &next);
+            nodes = next;
+        }
+
+        assert_eq!(history.len(), 2);
+
+        assert!(history.undo(&mut nodes));
+        assert!(history.undo(&mut nodes));
+        assert!(!history.undo(&mut nodes));
+
+        // the step from generation 0 was evicted
+        assert_eq!(nodes, [1, 1, 1]);
+    }
+
+    #[test]
+    fn unbounded_histories_grow_as_needed() {
+        let mut history = History::new(usize::MAX);
+        history.record(&[0, 1], &[1, 1]);
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.peek(&[1, 1]), Some(vec![0, 1]));
+
+        let mut disabled = History::new(0);
+        disabled.record(&[0], &[1]);
+
+        assert_eq!(disabled.len(), 0);
+    }
+}
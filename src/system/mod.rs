@@ -1,2 +1,10 @@
+pub mod agents;
+pub mod backend;
 pub mod dynamical_system;
+pub mod evolution;
+pub mod history;
+pub mod observers;
+pub mod settling;
+#[cfg(feature = "serde")]
+pub mod snapshot;
 pub mod utils;
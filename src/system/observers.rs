@@ -0,0 +1,283 @@
+use std::{
+    fmt::Debug,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use image::{Rgb, RgbImage};
+
+use crate::{error::Error, spaces::local::Stateable};
+
+/// Called by `DynamicalSystem` after the steps it was registered for, with the new generation
+/// number, the nodes it holds and the nodes of the generation before.
+pub trait Observer<N>: Send {
+    fn observe(&mut self, generation: u64, nodes: &[N], previous: &[N]);
+}
+
+impl<N, F> Observer<N> for F
+where
+    F: FnMut(u64, &[N], &[N]) + Send,
+{
+    fn observe(&mut self, generation: u64, nodes: &[N], previous: &[N]) {
+        self(generation, nodes, previous)
+    }
+}
+
+type SharedObserver<N> = Arc<Mutex<dyn Observer<N>>>;
+
+/// Observers registered on a system, each with the period it runs at. They are not cloned with
+/// the system: a clone starts without observers, so two systems never feed the same series.
+pub(crate) struct Observers<N> {
+    registered: Vec<(u64, SharedObserver<N>)>,
+}
+
+impl<N> Observers<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            registered: Vec::new(),
+        }
+    }
+
+    pub(crate) fn register(&mut self, every: u64, observer: SharedObserver<N>) {
+        self.registered.push((every.max(1), observer));
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.registered.clear();
+    }
+
+    pub(crate) fn notify(&self, generation: u64, nodes: &[N], previous: &[N]) {
+        for (every, observer) in &self.registered {
+            if generation.checked_rem(*every) == Some(0) {
+                observer
+                    .lock()
+                    .unwrap()
+                    .observe(generation, nodes, previous);
+            }
+        }
+    }
+}
+
+impl<N> Clone for Observers<N> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<N> Debug for Observers<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Observers({})", self.registered.len())
+    }
+}
+
+/// Fraction of nodes in a nonzero state.
+#[derive(Clone, Debug, Default)]
+pub struct Density {
+    pub series: Vec<(u64, f64)>,
+}
+
+impl Density {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<N: Stateable> Observer<N> for Density {
+    fn observe(&mut self, generation: u64, nodes: &[N], _previous: &[N]) {
+        let live = nodes.iter().filter(|n| n.state() != 0).count();
+
+        self.series
+            .push((generation, live as f64 / nodes.len().max(1) as f64));
+    }
+}
+
+/// Number of nodes in each state, indexed by state.
+#[derive(Clone, Debug, Default)]
+pub struct Population {
+    pub series: Vec<(u64, Vec<usize>)>,
+}
+
+impl Population {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<N: Stateable> Observer<N> for Population {
+    fn observe(&mut self, generation: u64, nodes: &[N], _previous: &[N]) {
+        let mut counts = Vec::new();
+
+        for node in nodes {
+            let state = node.state() as usize;
+
+            if state >= counts.len() {
+                counts.resize(state + 1, 0);
+            }
+
+            counts[state] += 1;
+        }
+
+        self.series.push((generation, counts));
+    }
+}
+
+/// Number of nodes whose state differs from the generation before. With a period above one this
+/// still only compares the last step.
+#[derive(Clone, Debug, Default)]
+pub struct ChangedCells {
+    pub series: Vec<(u64, usize)>,
+}
+
+impl ChangedCells {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<N: Stateable> Observer<N> for ChangedCells {
+    fn observe(&mut self, generation: u64, nodes: &[N], previous: &[N]) {
+        let changed = nodes
+            .iter()
+            .zip(previous)
+            .filter(|(a, b)| a.state() != b.state())
+            .count();
+
+        self.series.push((generation, changed));
+    }
+}
+
+type ColorMap<N> = Box<dyn Fn(&N) -> Rgb<u8> + Send>;
+
+/// Writes the nodes of a `width` by `height` grid to `directory/{prefix}_{generation}.png`.
+/// Failed writes, and generations whose node count does not match the grid, are kept in `errors`
+/// rather than interrupting the run.
+pub struct ImageSnapshot<N> {
+    directory: PathBuf,
+    prefix: String,
+    width: u32,
+    height: u32,
+    color_map: ColorMap<N>,
+    pub written: Vec<PathBuf>,
+    pub errors: Vec<Error>,
+}
+
+impl<N> ImageSnapshot<N> {
+    pub fn new(
+        directory: impl Into<PathBuf>,
+        prefix: impl Into<String>,
+        width: u32,
+        height: u32,
+        color_map: impl Fn(&N) -> Rgb<u8> + Send + 'static,
+    ) -> Self {
+        Self {
+            directory: directory.into(),
+            prefix: prefix.into(),
+            width,
+            height,
+            color_map: Box::new(color_map),
+            written: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl<N> Debug for ImageSnapshot<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImageSnapshot")
+            .field("directory", &self.directory)
+            .field("prefix", &self.prefix)
+            .field("written", &self.written.len())
+            .field("errors", &self.errors)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<N> Observer<N> for ImageSnapshot<N> {
+    fn observe(&mut self, generation: u64, nodes: &[N], _previous: &[N]) {
+        if nodes.len() != (self.width * self.height) as usize {
+            self.errors.push(Error::ImageSize {
+                width: self.width,
+                height: self.height,
+                nodes: nodes.len(),
+            });
+
+            return;
+        }
+
+        let img = RgbImage::from_fn(self.width, self.height, |x, y| {
+            (self.color_map)(&nodes[(y * self.width + x) as usize])
+        });
+
+        let path = self
+            .directory
+            .join(format!("{}_{generation:06}.png", self.prefix));
+
+        match img.save(&path) {
+            Ok(()) => self.written.push(path),
+            Err(err) => self.errors.push(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamics::implementations::life::LifeLike,
+        spaces::implementations::basic::{DiscreteState, HyperGraphHeap},
+        system::dynamical_system::DynamicalSystem,
+    };
+
+    #[test]
+    fn observers_run_at_their_period_and_stay_with_their_system() {
+        let nodes = (0..25)
+            .map(|i| DiscreteState::from_state(u32::from([11, 12, 13].contains(&i))))
+            .collect::<Vec<_>>();
+
+        let space = HyperGraphHeap::new_grid(&nodes, 5, 5, ());
+        let mut system =
+            DynamicalSystem::new(Box::new(space), Box::new(LifeLike::new(&[3], &[2, 3])));
+
+        let population = system.observe(Population::new());
+        let changed = system.observe_every(2, ChangedCells::new());
+
+        let mut clone = system.clone();
+
+        for _ in 0..4 {
+            system.compute_sync();
+            clone.compute_sync();
+        }
+
+        assert_eq!(population.lock().unwrap().series.len(), 4);
+        assert!(population
+            .lock()
+            .unwrap()
+            .series
+            .iter()
+            .all(|(_, counts)| counts == &vec![22, 3]));
+        assert_eq!(changed.lock().unwrap().series, vec![(2, 4), (4, 4)]);
+    }
+
+    #[test]
+    fn mismatched_snapshots_are_reported() {
+        let mut snapshot = ImageSnapshot::new(
+            std::env::temp_dir(),
+            "mismatch",
+            4,
+            4,
+            |_: &DiscreteState| Rgb([0, 0, 0]),
+        );
+
+        snapshot.observe(1, &vec![DiscreteState::from_state(0); 25], &[]);
+
+        assert!(snapshot.written.is_empty());
+        assert!(matches!(
+            snapshot.errors.as_slice(),
+            [Error::ImageSize {
+                width: 4,
+                height: 4,
+                nodes: 25
+            }]
+        ));
+    }
+}
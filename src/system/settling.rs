@@ -0,0 +1,245 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fmt::Debug,
+    hash::{Hash, Hasher},
+};
+
+use crate::{
+    dynamics::local::LocalDynamic,
+    error::Error,
+    spaces::local::{LocalHyperGraph, Stateable},
+    system::dynamical_system::DynamicalSystem,
+};
+
+/// Long-run behavior reached by a deterministic system, counted in steps from where the run
+/// started.
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
+pub enum Settling {
+    FixedPoint { transient: u64 },
+    Cycle { transient: u64, period: u64 },
+}
+
+impl Settling {
+    fn new(transient: u64, period: u64) -> Self {
+        if period == 1 {
+            Settling::FixedPoint { transient }
+        } else {
+            Settling::Cycle { transient, period }
+        }
+    }
+
+    pub fn transient(&self) -> u64 {
+        match self {
+            Settling::FixedPoint { transient } | Settling::Cycle { transient, .. } => *transient,
+        }
+    }
+
+    pub fn period(&self) -> u64 {
+        match self {
+            Settling::FixedPoint { .. } => 1,
+            Settling::Cycle { period, .. } => *period,
+        }
+    }
+}
+
+impl<S, D, N, E> DynamicalSystem<S, D, N, E>
+where
+    S: LocalHyperGraph<N, E> + Debug + Clone,
+    D: LocalDynamic<N, E> + Debug + Clone,
+    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
+{
+    /// Steps until a generation repeats or `max_steps` steps have run, whichever comes first,
+    /// and reports the fixed point or cycle found. Unless `stop_on_detection` is set, the run
+    /// keeps going to `max_steps` after detection.
+    ///
+    /// Generations are compared by hash (along with the generation before for second-order
+    /// dynamics), so a collision could in principle report a cycle early.
+    pub fn run_until_settled(
+        &mut self,
+        max_steps: u64,
+        stop_on_detection: bool,
+    ) -> Option<Settling> {
+        match self.try_run_until_settled(max_steps, stop_on_detection) {
+            Ok(settling) => settling,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Like `run_until_settled`, but reports a dynamic drawing random numbers, whose repeated
+    /// generations make no cycle, and the errors of `try_compute_sync` instead of panicking.
+    /// A failing step leaves the system at the generation before it.
+    ///
+    /// Generations only count as the same when they also sit at the same phase of the
+    /// dynamic's `generation_period`, since the rule applied to them differs otherwise.
+    pub fn try_run_until_settled(
+        &mut self,
+        max_steps: u64,
+        stop_on_detection: bool,
+    ) -> Result<Option<Settling>, Error> {
+        let period = self
+            .dynamic()
+            .generation_period()
+            .ok_or(Error::Stochastic)?;
+
+        let mut seen = HashMap::<u64, u64>::new();
+        let mut settling = None;
+
+        seen.insert(self.phase_hash(period), 0);
+
+        for step in 1..=max_steps {
+            self.try_compute_sync()?;
+
+            if settling.is_none() {
+                let hash = self.phase_hash(period);
+
+                if let Some(&first) = seen.get(&hash) {
+                    settling = Some(Settling::new(first, step - first));
+
+                    if stop_on_detection {
+                        break;
+                    }
+
+                    seen.clear();
+                } else {
+                    seen.insert(hash, step);
+                }
+            }
+        }
+
+        Ok(settling)
+    }
+
+    fn phase_hash(&self, period: u64) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        self.state_hash().hash(&mut hasher);
+        (self.generation() % period).hash(&mut hasher);
+
+        hasher.finish()
+    }
+
+    /// Hash of the current generation, plus the previous one for second-order dynamics.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        self.space().nodes().hash(&mut hasher);
+
+        if self.dynamic().is_second_order() {
+            self.previous_state().hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamics::{
+            combinators::{Alternating, Mixture},
+            implementations::{function::FnDynamic, life::LifeLike},
+        },
+        spaces::{
+            implementations::{
+                basic::{DiscreteState, HyperGraphHeap},
+                grid::ONE_DIMENSIONAL_NEIGHBORHOOD,
+            },
+            local::HyperEdge,
+        },
+    };
+
+    fn life(live: &[usize]) -> Option<Settling> {
+        let mut nodes = vec![DiscreteState::from_state(0); 36];
+
+        for &i in live {
+            nodes[i] = DiscreteState::from_state(1);
+        }
+
+        let space = HyperGraphHeap::new_grid(&nodes, 6, 6, ());
+        let mut system =
+            DynamicalSystem::new(Box::new(space), Box::new(LifeLike::new(&[3], &[2, 3])));
+
+        system.try_run_until_settled(20, true).unwrap()
+    }
+
+    #[test]
+    fn still_lifes_and_blinkers_settle() {
+        // a block, and a blinker across the middle row
+        assert_eq!(
+            life(&[14, 15, 20, 21]),
+            Some(Settling::FixedPoint { transient: 0 })
+        );
+        assert_eq!(
+            life(&[13, 14, 15]),
+            Some(Settling::Cycle {
+                transient: 0,
+                period: 2
+            })
+        );
+    }
+
+    fn keep(node: &DiscreteState, _: &[DiscreteState], _: Vec<&HyperEdge<()>>) -> DiscreteState {
+        node.clone()
+    }
+
+    fn copy_west(
+        _: &DiscreteState,
+        nodes: &[DiscreteState],
+        _: Vec<&HyperEdge<()>>,
+    ) -> DiscreteState {
+        nodes[0].clone()
+    }
+
+    type Row<D> =
+        DynamicalSystem<HyperGraphHeap<DiscreteState, (), (u32, u32)>, D, DiscreteState, ()>;
+
+    /// A ring of 4 nodes with the first one set.
+    fn ring<D: LocalDynamic<DiscreteState, ()> + Debug + Clone>(dynamic: D) -> Row<D> {
+        let mut nodes = vec![DiscreteState::from_state(0); 4];
+        nodes[0] = DiscreteState::from_state(1);
+
+        let space = HyperGraphHeap::new_grid_with_neighborhood(
+            &nodes,
+            4,
+            1,
+            &ONE_DIMENSIONAL_NEIGHBORHOOD,
+            (),
+        );
+
+        DynamicalSystem::new(Box::new(space), Box::new(dynamic))
+    }
+
+    #[test]
+    fn generations_repeating_at_another_phase_are_no_cycle() {
+        // the first step keeps the row as it is, but the next one moves it
+        let mut system = ring(Alternating::new(
+            FnDynamic::new(2, keep),
+            FnDynamic::new(2, copy_west),
+        ));
+
+        assert_eq!(
+            system.try_run_until_settled(20, true).unwrap(),
+            Some(Settling::Cycle {
+                transient: 0,
+                period: 8
+            })
+        );
+    }
+
+    #[test]
+    fn stochastic_dynamics_are_refused() {
+        let mut system = ring(Mixture::new(
+            0.5,
+            FnDynamic::new(2, keep),
+            FnDynamic::new(2, copy_west),
+        ));
+
+        assert!(matches!(
+            system.try_run_until_settled(20, true),
+            Err(Error::Stochastic)
+        ));
+        assert_eq!(system.generation(), 0);
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    io::{Read, Write},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    dynamics::local::LocalDynamic,
+    error::Error,
+    spaces::local::{LocalHyperGraph, Stateable},
+    system::dynamical_system::DynamicalSystem,
+};
+
+/// Version written into every snapshot. Loading rejects any other version.
+pub const FORMAT_VERSION: u32 = 1;
+
+const MAGIC: &[u8; 4] = b"GPCA";
+
+#[derive(Serialize)]
+struct SnapshotRef<'a, S, D, N> {
+    version: u32,
+    generation: u64,
+    seed: u64,
+    space: &'a S,
+    dynamic: &'a D,
+    previous: Option<&'a Vec<N>>,
+}
+
+#[derive(Deserialize)]
+struct Snapshot<S, D, N> {
+    version: u32,
+    generation: u64,
+    seed: u64,
+    space: S,
+    dynamic: D,
+    previous: Option<Vec<N>>,
+}
+
+fn check_version(found: u32) -> Result<(), Error> {
+    if found == FORMAT_VERSION {
+        Ok(())
+    } else {
+        Err(Error::Version {
+            found,
+            expected: FORMAT_VERSION,
+        })
+    }
+}
+
+impl<S, D, N, E> DynamicalSystem<S, D, N, E>
+where
+    S: LocalHyperGraph<N, E> + Debug + Clone + Serialize + DeserializeOwned,
+    D: LocalDynamic<N, E> + Debug + Clone + Serialize + DeserializeOwned,
+    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug + Serialize + DeserializeOwned,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
+{
+    fn snapshot(&self) -> SnapshotRef<'_, S, D, N> {
+        SnapshotRef {
+            version: FORMAT_VERSION,
+            generation: self.generation(),
+            seed: self.seed(),
+            space: self.space(),
+            dynamic: self.dynamic(),
+            previous: self.previous_state(),
+        }
+    }
+
+    fn restore(snapshot: Snapshot<S, D, N>) -> Self {
+        let mut system = DynamicalSystem::new(Box::new(snapshot.space), Box::new(snapshot.dynamic));
+
+        system.set_seed(snapshot.seed);
+        system.set_generation(snapshot.generation);
+
+        if let Some(previous) = snapshot.previous {
+            system.set_previous_state(previous);
+        }
+
+        system
+    }
+
+    /// Writes the space, dynamic, generation, seed and (for second-order dynamics) the previous
+    /// generation in the compact binary format. Observers and history are not part of a snapshot.
+    pub fn save_snapshot(&self, mut writer: impl Write) -> Result<(), Error> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+
+        bincode::serialize_into(&mut writer, &self.snapshot())
+            .map_err(|err| Error::Encoding(err.to_string()))?;
+
+        Ok(writer.flush()?)
+    }
+
+    pub fn load_snapshot(mut reader: impl Read) -> Result<Self, Error> {
+        let mut header = [0; 8];
+        reader.read_exact(&mut header)?;
+
+        if &header[..4] != MAGIC {
+            return Err(Error::Snapshot("missing GPCA header".to_string()));
+        }
+
+        check_version(u32::from_le_bytes([
+            header[4], header[5], header[6], header[7],
+        ]))?;
+
+        let snapshot: Snapshot<S, D, N> =
+            bincode::deserialize_from(reader).map_err(|err| Error::Snapshot(err.to_string()))?;
+        check_version(snapshot.version)?;
+
+        Ok(Self::restore(snapshot))
+    }
+
+    /// Same content as `save_snapshot`, as human-readable JSON.
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(&self.snapshot())
+            .map_err(|err| Error::Encoding(err.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|err| Error::Snapshot(err.to_string()))?;
+
+        let version = value
+            .get("version")
+            .and_then(|version| version.as_u64())
+            .ok_or_else(|| Error::Snapshot("missing version".to_string()))?;
+        check_version(u32::try_from(version).unwrap_or(u32::MAX))?;
+
+        let snapshot =
+            serde_json::from_value(value).map_err(|err| Error::Snapshot(err.to_string()))?;
+
+        Ok(Self::restore(snapshot))
+    }
+}
+
+/// Serializes maps as lists of pairs, for keys JSON cannot use as object keys.
+pub(crate) mod pairs {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<K, V, S>(map: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Ord,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(K, V)>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
+/// Serializes hash maps in key order, so that saving the same system twice gives the same bytes.
+pub(crate) mod sorted {
+    use std::collections::{BTreeMap, HashMap};
+    use std::hash::Hash;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize + Ord,
+        V: Serialize,
+        S: Serializer,
+    {
+        map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        HashMap::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamics::implementations::{life::LifeLike, second_order::SecondOrder},
+        spaces::implementations::basic::{DiscreteState, HyperGraphHeap},
+    };
+
+    type Space = HyperGraphHeap<DiscreteState, (), (u32, u32)>;
+    type System = DynamicalSystem<Space, SecondOrder<LifeLike>, DiscreteState, ()>;
+
+    fn system() -> System {
+        let nodes = (0..64)
+            .map(|i| DiscreteState::from_state(u32::from(i % 3 == 0 || i % 7 == 0)))
+            .collect::<Vec<_>>();
+
+        let space = HyperGraphHeap::new_grid(&nodes, 8, 8, ());
+        let dynamic = SecondOrder::new(LifeLike::new(&[3], &[2, 3]));
+
+        let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
+        system.set_seed(17);
+
+        for _ in 0..5 {
+            system.compute_sync();
+        }
+
+        system
+    }
+
+    #[test]
+    fn binary_and_json_snapshots_round_trip() {
+        let mut original = system();
+
+        let mut bytes = Vec::new();
+        original.save_snapshot(&mut bytes).unwrap();
+
+        let mut from_bytes = System::load_snapshot(bytes.as_slice()).unwrap();
+        let mut from_json = System::from_json(&original.to_json().unwrap()).unwrap();
+
+        for restored in [&from_bytes, &from_json] {
+            assert_eq!(restored.generation(), original.generation());
+            assert_eq!(restored.seed(), original.seed());
+            assert_eq!(restored.space_state(), original.space_state());
+            assert_eq!(restored.previous_state(), original.previous_state());
+        }
+
+        for _ in 0..5 {
+            original.compute_sync();
+            from_bytes.compute_sync();
+            from_json.compute_sync();
+        }
+
+        assert_eq!(from_bytes.space_state(), original.space_state());
+        assert_eq!(from_json.space_state(), original.space_state());
+    }
+
+    #[test]
+    fn snapshots_are_deterministic() {
+        let (mut first, mut second) = (Vec::new(), Vec::new());
+
+        system().save_snapshot(&mut first).unwrap();
+        system().save_snapshot(&mut second).unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn other_versions_are_refused() {
+        let mut bytes = Vec::new();
+        system().save_snapshot(&mut bytes).unwrap();
+        bytes[4] += 1;
+
+        assert!(matches!(
+            System::load_snapshot(bytes.as_slice()),
+            Err(Error::Version { .. })
+        ));
+    }
+}
@@ -5,6 +5,7 @@ use rayon::iter::ParallelIterator;
 
 use crate::{
     dynamics::local::LocalDynamic,
+    error::Error,
     spaces::{implementations::basic::HyperGraphHeap, local::Stateable},
 };
 
@@ -17,6 +18,22 @@ pub fn generate_image_from_space<N, D>(
     system: &System<N, D>,
     color_map: &(dyn Fn(&N) -> Rgb<u8> + Sync),
 ) -> RgbImage
+where
+    N: Stateable + Send + Sync + Clone + Debug,
+    D: LocalDynamic<N, ()> + Debug + Clone,
+{
+    match try_generate_image_from_space(system, color_map) {
+        Ok(img) => img,
+        Err(err) => panic!("{err}"),
+    }
+}
+
+/// Like `generate_image_from_space`, but reports a payload whose width and height don't match
+/// the number of nodes instead of panicking.
+pub fn try_generate_image_from_space<N, D>(
+    system: &System<N, D>,
+    color_map: &(dyn Fn(&N) -> Rgb<u8> + Sync),
+) -> Result<RgbImage, Error>
 where
     N: Stateable + Send + Sync + Clone + Debug,
     D: LocalDynamic<N, ()> + Debug + Clone,
@@ -25,6 +42,14 @@ where
 
     let binding = system.space_state();
 
+    if *width as u64 * *height as u64 != binding.len() as u64 {
+        return Err(Error::ImageSize {
+            width: *width,
+            height: *height,
+            nodes: binding.len(),
+        });
+    }
+
     let mut img = RgbImage::new(*width, *height);
 
     img.par_enumerate_pixels_mut().for_each(|(x, y, pixel)| {
@@ -33,5 +58,5 @@ where
         *pixel = color; // Rgb([color.r, color.g, color.b]);
     });
 
-    img
+    Ok(img)
 }
@@ -0,0 +1,384 @@
+use std::{fmt::Debug, hash::Hash};
+
+use wgpu::ShaderSource;
+
+use crate::{
+    dynamics::local::LocalDynamic,
+    error::Error,
+    spaces::{
+        implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::grid_offsets,
+        },
+        lattice::LatticeComputable,
+        local::{LocalHyperGraph, Stateable},
+    },
+    system::dynamical_system::DynamicalSystem,
+};
+
+/// Lookup-table rules the WGSL generator knows how to emit. The tables are not part of the
+/// shader: kernels read them from a storage buffer filled with `table`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WgslRule {
+    /// `table[center + sum of neighbors]`.
+    Totalistic { table: Vec<u32> },
+    /// `table[center * sums + sum of neighbors]`.
+    OuterTotalistic { sums: usize, table: Vec<u32> },
+    /// `table[center, n1, .., nk]` read as base `states` digits, the center most significant.
+    Table { table: Vec<u32> },
+    /// `table[center * (neighbors + 1) + count]`, where `count` is the number of neighbors in
+    /// state `targets[center]`.
+    Counting { targets: Vec<u32>, table: Vec<u32> },
+}
+
+impl WgslRule {
+    /// Contents of the `table` buffer: the targets of a counting rule followed by its table, the
+    /// table alone otherwise.
+    pub fn table(&self) -> Vec<u32> {
+        match self {
+            WgslRule::Totalistic { table }
+            | WgslRule::OuterTotalistic { table, .. }
+            | WgslRule::Table { table } => table.clone(),
+            WgslRule::Counting { targets, table } => [&targets[..], &table[..]].concat(),
+        }
+    }
+
+    /// Checks the tables cover every index the kernel can compute for `neighbors` neighbors.
+    fn check(&self, states: u32, neighbors: usize) -> Result<(), Error> {
+        let k = states.saturating_sub(1) as usize;
+
+        let (expected, found) = match self {
+            // larger tables serve larger neighborhoods too
+            WgslRule::Totalistic { table } => {
+                let expected = (neighbors + 1) * k + 1;
+
+                (expected, table.len().min(expected))
+            }
+            WgslRule::OuterTotalistic { sums, table } => {
+                if *sums < neighbors * k + 1 {
+                    return Err(kernel_error(format!(
+                        "{sums} sums do not cover {neighbors} neighbors in {states} states"
+                    )));
+                }
+
+                (states as usize * sums, table.len())
+            }
+            WgslRule::Table { table } => {
+                let configurations = u32::try_from(neighbors + 1)
+                    .ok()
+                    .and_then(|cells| (states as usize).checked_pow(cells))
+                    .ok_or(Error::Configurations {
+                        states,
+                        cells: neighbors + 1,
+                    })?;
+
+                (configurations, table.len())
+            }
+            WgslRule::Counting { targets, table } => {
+                if targets.len() != states as usize {
+                    return Err(kernel_error(format!(
+                        "{} targets for {states} states",
+                        targets.len()
+                    )));
+                }
+
+                (states as usize * (neighbors + 1), table.len())
+            }
+        };
+
+        if expected != found {
+            return Err(kernel_error(format!(
+                "{neighbors} neighbors in {states} states need a table of {expected} entries, not {found}"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+fn kernel_error(message: impl Into<String>) -> Error {
+    Error::Kernel(message.into())
+}
+
+/// Dynamics with a generated WGSL kernel, which makes their systems on `DiscreteState` grids
+/// `LatticeComputable`.
+pub trait GridKernel {
+    /// Number of states and rule of the kernel for a grid linked by `offsets`.
+    fn kernel_rule(&self, offsets: &[(i32, i32)]) -> (u32, WgslRule);
+
+    /// Shader for a grid linked by `offsets`. It binds `input` (0), `result` (1), a `params`
+    /// uniform (3) starting with the width and height, and the table of `kernel_rule` as a
+    /// read-only storage buffer (4).
+    fn kernel_wgsl(&self, offsets: &[(i32, i32)]) -> Result<String, Error> {
+        let (states, rule) = self.kernel_rule(offsets);
+
+        generate_wgsl(states, offsets, &rule)
+    }
+}
+
+/// Emits a kernel applying `rule` to a periodic grid linked by `offsets`, which are baked into
+/// the shader. Of the `params` uniform only the width and height are read. Tables that do not
+/// match the neighborhood are reported rather than read out of bounds on the GPU.
+pub fn generate_wgsl(
+    states: u32,
+    offsets: &[(i32, i32)],
+    rule: &WgslRule,
+) -> Result<String, Error> {
+    if offsets.is_empty() {
+        return Err(kernel_error("kernels need at least one neighbor"));
+    }
+
+    rule.check(states, offsets.len())?;
+
+    let update = match rule {
+        WgslRule::Totalistic { .. } => "    var sum: u32 = center;
+
+    for (var i: u32 = 0u; i < NEIGHBOURS; i = i + 1u) {
+        sum = sum + neighbour(x, y, i);
+    }
+
+    let next: u32 = table.data[sum];"
+            .to_string(),
+        WgslRule::OuterTotalistic { sums, .. } => format!(
+            "    var sum: u32 = 0u;
+
+    for (var i: u32 = 0u; i < NEIGHBOURS; i = i + 1u) {{
+        sum = sum + neighbour(x, y, i);
+    }}
+
+    let next: u32 = table.data[center * {sums}u + sum];"
+        ),
+        WgslRule::Table { .. } => format!(
+            "    var index: u32 = center;
+
+    for (var i: u32 = 0u; i < NEIGHBOURS; i = i + 1u) {{
+        index = index * {states}u + neighbour(x, y, i);
+    }}
+
+    let next: u32 = table.data[index];"
+        ),
+        WgslRule::Counting { .. } => format!(
+            "    let target_state: u32 = table.data[center];
+    var count: u32 = 0u;
+
+    for (var i: u32 = 0u; i < NEIGHBOURS; i = i + 1u) {{
+        count = count + u32(neighbour(x, y, i) == target_state);
+    }}
+
+    let next: u32 = table.data[{states}u + center * (NEIGHBOURS + 1u) + count];"
+        ),
+    };
+
+    let offsets_list = offsets
+        .iter()
+        .map(|(dx, dy)| format!("vec2<i32>({dx}, {dy})"))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    Ok(format!(
+        "// Generated by gpca from a {states}-state rule over {neighbours} neighbours.
+
+struct Image {{
+    data: array<f32>,
+}};
+
+struct Table {{
+    data: array<u32>,
+}};
+
+struct Params {{
+    image_width: u32,
+    image_height: u32,
+}};
+
+@group(0) @binding(0)
+var<storage, read_write> input: Image;
+
+@group(0) @binding(1)
+var<storage, read_write> result: Image;
+
+@group(0) @binding(3)
+var<uniform> params: Params;
+
+@group(0) @binding(4)
+var<storage, read> table: Table;
+
+const NEIGHBOURS: u32 = {neighbours}u;
+
+var<private> offsets: array<vec2<i32>, {neighbours}> = array<vec2<i32>, {neighbours}>({offsets_list});
+
+// Periodic boundaries, as in `new_grid`. The remainder is only taken of non-negative values,
+// since backends disagree on its sign for negative ones.
+fn wrap(value: i32, size: u32) -> u32 {{
+    let s: i32 = i32(size);
+    return u32(value + s * (1 + abs(value) / s)) % size;
+}}
+
+fn neighbour(x: u32, y: u32, i: u32) -> u32 {{
+    let nx: u32 = wrap(i32(x) + offsets[i].x, params.image_width);
+    let ny: u32 = wrap(i32(y) + offsets[i].y, params.image_height);
+
+    return u32(input.data[ny * params.image_width + nx]);
+}}
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {{
+    var x: u32 = global_id.x;
+    var y: u32 = global_id.y;
+
+    if (x >= params.image_width || y >= params.image_height) {{
+        return;
+    }}
+
+    var index_0: u32 = y * params.image_width + x;
+    let center: u32 = u32(input.data[index_0]);
+
+{update}
+
+    result.data[index_0] = f32(next);
+}}
+",
+        neighbours = offsets.len(),
+    ))
+}
+
+/// Parses and validates `source` with naga, so shaders can be checked without a GPU.
+pub fn validate_wgsl(source: &str) -> Result<(), Error> {
+    let module = wgpu::naga::front::wgsl::parse_str(source)
+        .map_err(|err| Error::Shader(err.emit_to_string(source)))?;
+
+    wgpu::naga::valid::Validator::new(
+        wgpu::naga::valid::ValidationFlags::all(),
+        wgpu::naga::valid::Capabilities::empty(),
+    )
+    .validate(&module)
+    .map_err(|err| Error::Shader(err.emit_to_string(source)))?;
+
+    Ok(())
+}
+
+impl<N, E, D> LatticeComputable<N, E>
+    for DynamicalSystem<HyperGraphHeap<DiscreteState, (), (u32, u32)>, D, DiscreteState, ()>
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable + Debug,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized + Debug,
+    D: GridKernel + LocalDynamic<DiscreteState, ()> + Debug + Clone,
+{
+    fn shape(&self) -> Vec<usize> {
+        let (w, h) = self.space().payload();
+
+        vec![*w as usize, *h as usize]
+    }
+
+    fn observation_neighbors(&self) -> Result<Vec<Vec<i32>>, Error> {
+        let (w, h) = self.space().payload();
+
+        grid_offsets(self.space().node_neighbors(), *w, *h)
+    }
+
+    fn update_wgsl_code(&self) -> Result<ShaderSource<'_>, Error> {
+        let offsets = kernel_offsets(<_ as LatticeComputable<N, E>>::observation_neighbors(self)?);
+
+        Ok(ShaderSource::Wgsl(
+            self.dynamic().kernel_wgsl(&offsets)?.into(),
+        ))
+    }
+
+    fn wgsl_params(&self) -> Vec<u32> {
+        let (w, h) = self.space().payload();
+        // as many as the offsets on the grids `observation_neighbors` accepts
+        let neighbors = self.space().node_neighbors().get(&0).map_or(0, Vec::len) as u32;
+
+        vec![*w, *h, neighbors]
+    }
+
+    fn wgsl_table(&self) -> Result<Vec<u32>, Error> {
+        let offsets = kernel_offsets(<_ as LatticeComputable<N, E>>::observation_neighbors(self)?);
+
+        Ok(self.dynamic().kernel_rule(&offsets).1.table())
+    }
+}
+
+fn kernel_offsets(observation: Vec<Vec<i32>>) -> Vec<(i32, i32)> {
+    observation
+        .iter()
+        .map(|offset| (offset[0], offset[1]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dynamics::{context::NodeRng, implementations::neighborhood_table::NeighborhoodTable},
+        spaces::implementations::grid::{MOORE_NEIGHBORHOOD, VON_NEUMANN_NEIGHBORHOOD},
+    };
+
+    const STATES: u32 = 3;
+
+    /// One rule of each variant sized for `neighbors` neighbors in `STATES` states.
+    fn rules(neighbors: usize) -> Vec<WgslRule> {
+        let k = STATES as usize;
+        let sums = neighbors * (k - 1) + 1;
+
+        vec![
+            WgslRule::Totalistic {
+                table: vec![1; (neighbors + 1) * (k - 1) + 1],
+            },
+            WgslRule::OuterTotalistic {
+                sums,
+                table: vec![1; k * sums],
+            },
+            WgslRule::Table {
+                table: vec![1; k.pow(neighbors as u32 + 1)],
+            },
+            WgslRule::Counting {
+                targets: vec![1; k],
+                table: vec![1; k * (neighbors + 1)],
+            },
+        ]
+    }
+
+    #[test]
+    fn every_rule_generates_valid_wgsl() {
+        for offsets in [&MOORE_NEIGHBORHOOD[..], &VON_NEUMANN_NEIGHBORHOOD[..]] {
+            for rule in rules(offsets.len()) {
+                let source = generate_wgsl(STATES, offsets, &rule).unwrap();
+
+                if let Err(err) = validate_wgsl(&source) {
+                    panic!("{rule:?} over {} neighbors: {err}", offsets.len());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mismatched_tables_are_refused() {
+        // sized for the von Neumann neighborhood, too small for the Moore one
+        for rule in rules(VON_NEUMANN_NEIGHBORHOOD.len()) {
+            assert!(
+                matches!(
+                    generate_wgsl(STATES, &MOORE_NEIGHBORHOOD, &rule),
+                    Err(Error::Kernel(_))
+                ),
+                "{rule:?}"
+            );
+        }
+
+        assert!(matches!(
+            generate_wgsl(STATES, &[], &rules(0)[0]),
+            Err(Error::Kernel(_))
+        ));
+    }
+
+    #[test]
+    fn neighborhood_tables_refuse_other_neighborhoods() {
+        let table = NeighborhoodTable::random(2, 8, &mut NodeRng::new(0));
+
+        assert!(table.kernel_wgsl(&MOORE_NEIGHBORHOOD).is_ok());
+        assert!(matches!(
+            table.kernel_wgsl(&VON_NEUMANN_NEIGHBORHOOD),
+            Err(Error::Kernel(_))
+        ));
+    }
+}
@@ -1,3 +1,5 @@
+pub mod codegen;
 #[cfg(feature = "fdg")]
 pub mod fdg_macroquad;
+pub mod session;
 pub mod wgpu;
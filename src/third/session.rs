@@ -0,0 +1,335 @@
+use std::hash::Hash;
+
+use wgpu::util::DeviceExt;
+
+use crate::{
+    error::Error,
+    spaces::{
+        lattice::{table_data, LatticeComputable},
+        local::Stateable,
+    },
+    system::backend::BackendKind,
+    third::wgpu::{pop_error_scope, read_buffer, GpuDevice, Real},
+};
+
+/// A lattice kept on the GPU between generations. Two storage buffers take turns as the input
+/// and the result of the kernel, so stepping needs no uploads and the pipeline is built once;
+/// the cells only come back to the CPU through `read`.
+pub struct GpuSession<'a> {
+    device: &'a GpuDevice,
+    cells: [wgpu::Buffer; 2],
+    // bind_groups[i] reads cells[i] and writes the other buffer
+    bind_groups: [wgpu::BindGroup; 2],
+    pipeline: wgpu::ComputePipeline,
+    staging: wgpu::Buffer,
+    current: usize,
+    width: u32,
+    height: u32,
+    generation: u64,
+    // the generation before the current one is in the other buffer once a dispatch ran
+    stepped: bool,
+}
+
+impl<'a> GpuSession<'a> {
+    /// Uploads `cells`, row-major over the shape of `lattice`, and compiles its kernel.
+    /// `generation` is the generation the cells belong to. A kernel the device rejects is
+    /// reported as `Error::Gpu`, a space the kernel cannot run on as the error of
+    /// `observation_neighbors`.
+    pub fn new<N, E>(
+        device: &'a GpuDevice,
+        lattice: &impl LatticeComputable<N, E>,
+        cells: &[Real],
+        generation: u64,
+    ) -> Result<Self, Error>
+    where
+        N: Clone + Sync + Send + Hash + Eq + Stateable,
+        E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+    {
+        let shape = lattice.shape();
+
+        if shape.len() != 2 {
+            return Err(Error::UnsupportedShape {
+                dimensions: shape.len(),
+            });
+        }
+
+        let (width, height) = (shape[0] as u32, shape[1] as u32);
+
+        if width as u64 * height as u64 != cells.len() as u64 {
+            return Err(Error::ImageSize {
+                width,
+                height,
+                nodes: cells.len(),
+            });
+        }
+
+        // everything that can fail on the CPU is done before the scope is pushed, so that no early
+        // return leaves it on the device
+        let neighbors = lattice
+            .observation_neighbors()?
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<i32>>();
+        let table = table_data(lattice.wgsl_table()?);
+        let source = lattice.update_wgsl_code()?;
+
+        device
+            .device
+            .push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let cell_usage = wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC;
+
+        let cells = [0, 1].map(|i| {
+            device
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(if i == 0 { "cells 0" } else { "cells 1" }),
+                    contents: bytemuck::cast_slice(cells),
+                    usage: cell_usage,
+                })
+        });
+
+        let neighbors_buffer =
+            device.create_data_buffer("neighbors", bytemuck::cast_slice(&neighbors[..]));
+
+        let params = lattice.wgsl_params();
+        let params_data = bytemuck::cast_slice(&params);
+        let params_buffer = device.create_uniform_buffer("params", params_data);
+        let table_buffer = device.create_data_buffer("table", &table);
+
+        let storage = wgpu::BufferBindingType::Storage { read_only: false };
+        let types = [
+            (4, storage),
+            (4, storage),
+            (8, storage),
+            (params_data.len() as u64, wgpu::BufferBindingType::Uniform),
+            (4, wgpu::BufferBindingType::Storage { read_only: true }),
+        ];
+
+        let layout_entries = types
+            .iter()
+            .enumerate()
+            .map(|(index, (size, ty))| wgpu::BindGroupLayoutEntry {
+                binding: index as u32,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: *ty,
+                    has_dynamic_offset: false,
+                    min_binding_size: wgpu::BufferSize::new(*size),
+                },
+                count: None,
+            })
+            .collect::<Vec<_>>();
+
+        let layout = device
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &layout_entries,
+            });
+
+        let bind_groups = [0, 1].map(|i| {
+            let buffers = [
+                &cells[i],
+                &cells[1 - i],
+                &neighbors_buffer,
+                &params_buffer,
+                &table_buffer,
+            ];
+
+            let entries = buffers
+                .iter()
+                .enumerate()
+                .map(|(index, buffer)| wgpu::BindGroupEntry {
+                    binding: index as u32,
+                    resource: buffer.as_entire_binding(),
+                })
+                .collect::<Vec<_>>();
+
+            device.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &layout,
+                entries: &entries,
+            })
+        });
+
+        let module = device
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source,
+            });
+
+        let pipeline_layout =
+            device
+                .device
+                .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: None,
+                    bind_group_layouts: &[&layout],
+                    push_constant_ranges: &[],
+                });
+
+        let pipeline = device
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: Some(&pipeline_layout),
+                module: &module,
+                entry_point: "main",
+                cache: None,
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            });
+
+        let staging = device.create_output_buffer("staging", cells[0].size());
+
+        if let Some(err) = pop_error_scope(device) {
+            return Err(Error::Gpu(err.to_string()));
+        }
+
+        Ok(Self {
+            device,
+            cells,
+            bind_groups,
+            pipeline,
+            staging,
+            current: 0,
+            width,
+            height,
+            generation,
+            stepped: false,
+        })
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn backend(&self) -> BackendKind {
+        self.device.backend()
+    }
+
+    /// Records `generations` dispatches in one command buffer and submits it once.
+    pub fn run(&mut self, generations: u32) {
+        let mut encoder = self
+            .device
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        {
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+            });
+            cpass.set_pipeline(&self.pipeline);
+
+            for _ in 0..generations {
+                cpass.set_bind_group(0, &self.bind_groups[self.current], &[]);
+                cpass.dispatch_workgroups(self.width, self.height, 1);
+
+                self.current = 1 - self.current;
+            }
+        }
+
+        self.device.queue.submit(Some(encoder.finish()));
+        self.generation += generations as u64;
+        self.stepped |= generations > 0;
+    }
+
+    /// Copies the current generation back to the CPU, waiting for pending dispatches.
+    pub fn read(&self) -> Result<Vec<Real>, Error> {
+        self.read_cells(self.current)
+    }
+
+    /// Copies the generation before the current one back to the CPU, if the session stepped.
+    pub fn read_previous(&self) -> Result<Option<Vec<Real>>, Error> {
+        if !self.stepped {
+            return Ok(None);
+        }
+
+        self.read_cells(1 - self.current).map(Some)
+    }
+
+    fn read_cells(&self, index: usize) -> Result<Vec<Real>, Error> {
+        let mut encoder = self
+            .device
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        encoder.copy_buffer_to_buffer(&self.cells[index], 0, &self.staging, 0, self.staging.size());
+        self.device.queue.submit(Some(encoder.finish()));
+
+        read_buffer(self.device, &self.staging)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    use super::*;
+    use crate::{
+        dynamics::{
+            context::NodeRng,
+            implementations::{life::LifeLike, neighborhood_table::NeighborhoodTable},
+        },
+        spaces::implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::one_dimensional_neighborhood,
+        },
+        system::dynamical_system::DynamicalSystem,
+        third::wgpu::{select_device, ComputeDevice},
+    };
+
+    #[tokio::test]
+    async fn refused_spaces_leave_later_errors_reported() {
+        let ComputeDevice::Gpu(device) = select_device().await else {
+            eprintln!("no GPU adapter, skipping");
+            return;
+        };
+
+        let nodes = (0..9)
+            .map(|i| DiscreteState::from_state(i % 2))
+            .collect::<Vec<_>>();
+        // four neighbors, but not the von Neumann ones
+        let ring = HyperGraphHeap::new_grid_with_neighborhood(
+            &nodes,
+            9,
+            1,
+            &one_dimensional_neighborhood(2),
+            (),
+        );
+        let system = DynamicalSystem::new(Box::new(ring), Box::new(LifeLike::new(&[3], &[2, 3])));
+
+        assert!(matches!(
+            system.gpu_session(&device),
+            Err(Error::NotAGrid { .. })
+        ));
+
+        // a table over four neighbors on a Moore grid fails to generate a kernel
+        let grid = HyperGraphHeap::new_grid(&nodes, 3, 3, ());
+        let table = NeighborhoodTable::random(2, 4, &mut NodeRng::new(0));
+        let system = DynamicalSystem::new(Box::new(grid), Box::new(table));
+
+        assert!(matches!(system.gpu_session(&device), Err(Error::Kernel(_))));
+
+        // a scope left on the device would swallow the error of this pipeline
+        let reported = Arc::new(AtomicBool::new(false));
+        let flag = reported.clone();
+        device
+            .device
+            .on_uncaptured_error(Box::new(move |_| flag.store(true, Ordering::SeqCst)));
+
+        device
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl("@compute fn main( {".into()),
+            });
+
+        assert!(reported.load(Ordering::SeqCst));
+    }
+}
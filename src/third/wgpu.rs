@@ -1,4 +1,8 @@
-use crate::spaces::{lattice::LatticeComputable, local::Stateable};
+use crate::{
+    error::Error,
+    spaces::{lattice::LatticeComputable, local::Stateable},
+    system::backend::BackendKind,
+};
 
 use std::hash::Hash;
 use wgpu::util::DeviceExt;
@@ -19,6 +23,22 @@ pub struct Image {
 pub struct GpuDevice {
     pub(crate) device: wgpu::Device,
     pub(crate) queue: wgpu::Queue,
+    pub(crate) info: wgpu::AdapterInfo,
+}
+
+/// Device picked by `select_device`: a GPU adapter when one exists, the CPU otherwise.
+pub enum ComputeDevice {
+    Gpu(GpuDevice),
+    Cpu,
+}
+
+impl ComputeDevice {
+    pub fn backend(&self) -> BackendKind {
+        match self {
+            ComputeDevice::Gpu(device) => device.backend(),
+            ComputeDevice::Cpu => BackendKind::CpuRayon,
+        }
+    }
 }
 
 pub struct Kernel {
@@ -33,14 +53,46 @@ pub async fn run<N, E>(
     shader: wgpu::ShaderSource<'_>,
 
     dynamic: &impl LatticeComputable<N, E>,
-) -> Image
+) -> Result<Image, Error>
 where
     N: Clone + Sync + Send + Hash + Eq + Stateable,
     E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
 {
+    run_blocking(device, image, kernel, shader, dynamic)
+}
+
+/// `run` without the future: the dispatch is waited on by polling the device. Validation and
+/// out-of-memory errors, such as a shader that does not compile, are reported as `Error::Gpu`
+/// instead of reaching the device's panicking error handler.
+pub fn run_blocking<N, E>(
+    device: &GpuDevice,
+    image: &Image,
+    kernel: &Kernel,
+    shader: wgpu::ShaderSource<'_>,
+
+    dynamic: &impl LatticeComputable<N, E>,
+) -> Result<Image, Error>
+where
+    N: Clone + Sync + Send + Hash + Eq + Stateable,
+    E: Clone + Sync + Send + Eq + PartialEq + Hash + Sized,
+{
+    device
+        .device
+        .push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+    device
+        .device
+        .push_error_scope(wgpu::ErrorFilter::Validation);
+
     let (mut output, output_size, result_buffer, output_buffer, bind_group, compute_pipeline) =
-        dynamic.wgsl_compute(kernel, image, device, shader);
-    // fun_name(kernel, image, device, shader);
+        match dynamic.wgsl_compute(kernel, image, device, shader) {
+            Ok(computed) => computed,
+            Err(err) => {
+                pop_error_scope(device);
+                pop_error_scope(device);
+
+                return Err(err);
+            }
+        };
 
     // encode and run commands
     let mut encoder = device
@@ -60,56 +112,149 @@ where
     encoder.copy_buffer_to_buffer(&result_buffer, 0, &output_buffer, 0, output_size);
     device.queue.submit(Some(encoder.finish()));
 
-    // read output_buffer
-    let buffer_slice = output_buffer.slice(..);
+    // both scopes are popped before either error is reported
+    let validation = pop_error_scope(device);
+    let out_of_memory = pop_error_scope(device);
+
+    if let Some(err) = validation.or(out_of_memory) {
+        return Err(Error::Gpu(err.to_string()));
+    }
+
+    output.data = read_buffer(device, &output_buffer)?;
+
+    Ok(output)
+}
+
+/// Pops the innermost error scope. Native devices resolve it right away, so the future is polled
+/// once rather than awaited.
+pub(crate) fn pop_error_scope(device: &GpuDevice) -> Option<wgpu::Error> {
+    let future = std::pin::pin!(device.device.pop_error_scope());
+    let mut context = std::task::Context::from_waker(std::task::Waker::noop());
 
-    buffer_slice.map_async(wgpu::MapMode::Read, |_r| {});
+    match std::future::Future::poll(future, &mut context) {
+        std::task::Poll::Ready(err) => err,
+        std::task::Poll::Pending => None,
+    }
+}
+
+/// Maps `buffer`, waits for the device and copies its content out, reporting a failed mapping.
+pub(crate) fn read_buffer(device: &GpuDevice, buffer: &wgpu::Buffer) -> Result<Vec<Real>, Error> {
+    let buffer_slice = buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+        // the receiver outlives the poll below, which runs this callback
+        let _ = sender.send(result);
+    });
 
     device.device.poll(wgpu::Maintain::Wait);
 
+    receiver
+        .recv()
+        .map_err(|_| Error::Gpu("the buffer was never mapped".to_string()))?
+        .map_err(|err| Error::Gpu(err.to_string()))?;
+
     let data = buffer_slice.get_mapped_range();
-    output.data = bytemuck::cast_slice::<u8, f32>(&data).to_vec();
+    let cells = bytemuck::cast_slice::<u8, Real>(&data).to_vec();
 
     // We have to make sure all mapped views are dropped before we unmap the buffer.
     drop(data);
-    output_buffer.unmap();
+    buffer.unmap();
 
-    output
+    Ok(cells)
 }
 
 pub async fn create_gpu_device() -> GpuDevice {
-    let (device, queue) = create_device_queue().await;
-    GpuDevice { device, queue }
+    match try_create_gpu_device().await {
+        Ok(device) => device,
+        Err(err) => panic!("{err}"),
+    }
+}
+
+pub async fn try_create_gpu_device() -> Result<GpuDevice, Error> {
+    request_gpu_device(false).await
+}
+
+/// Tries a hardware adapter, then a software (`force_fallback_adapter`) one, and settles for the
+/// CPU when neither is available.
+pub async fn select_device() -> ComputeDevice {
+    for force_fallback_adapter in [false, true] {
+        if let Ok(device) = request_gpu_device(force_fallback_adapter).await {
+            return ComputeDevice::Gpu(device);
+        }
+    }
+
+    ComputeDevice::Cpu
 }
 
 pub async fn create_device_queue() -> (wgpu::Device, wgpu::Queue) {
+    let device = create_gpu_device().await;
+    (device.device, device.queue)
+}
+
+/// Like `create_device_queue`, but reports a missing adapter or a refused device instead of
+/// panicking.
+pub async fn try_create_device_queue() -> Result<(wgpu::Device, wgpu::Queue), Error> {
+    let device = try_create_gpu_device().await?;
+    Ok((device.device, device.queue))
+}
+
+async fn request_gpu_device(force_fallback_adapter: bool) -> Result<GpuDevice, Error> {
     let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
     let adapter = instance
         .request_adapter(&wgpu::RequestAdapterOptions {
             power_preference: wgpu::PowerPreference::HighPerformance,
             compatible_surface: None,
-            force_fallback_adapter: false,
+            force_fallback_adapter,
         })
         .await
-        .expect("Failed to find an appropriate adapter");
+        .ok_or(Error::NoAdapter)?;
+
+    // software adapters fall short of the default limits, so settle for the downlevel ones there
+    let supported = adapter.limits();
+    let base = if wgpu::Limits::default().check_limits(&supported) {
+        wgpu::Limits::default()
+    } else {
+        wgpu::Limits::downlevel_defaults()
+    };
 
-    adapter
+    let (device, queue) = adapter
         .request_device(
             &wgpu::DeviceDescriptor {
                 label: None,
                 required_limits: wgpu::Limits {
-                    max_storage_buffer_binding_size: 512 * 1024 * 1024,
-                    ..Default::default()
+                    max_storage_buffer_binding_size: supported
+                        .max_storage_buffer_binding_size
+                        .min(512 * 1024 * 1024),
+                    ..base
                 },
                 ..Default::default()
             },
             None,
         )
-        .await
-        .expect("Failed to create device")
+        .await?;
+
+    Ok(GpuDevice {
+        device,
+        queue,
+        info: adapter.get_info(),
+    })
 }
 
 impl GpuDevice {
+    pub fn adapter_info(&self) -> &wgpu::AdapterInfo {
+        &self.info
+    }
+
+    /// `SoftwareGpu` for adapters that emulate a GPU on the CPU, such as the fallback adapter.
+    pub fn backend(&self) -> BackendKind {
+        if self.info.device_type == wgpu::DeviceType::Cpu {
+            BackendKind::SoftwareGpu
+        } else {
+            BackendKind::Gpu
+        }
+    }
+
     pub fn create_buffer(&self, label: &str, size: u64) -> wgpu::Buffer {
         self.device.create_buffer(&wgpu::BufferDescriptor {
             label: Some(label),
@@ -247,7 +392,7 @@ impl std::ops::Index<(u32, u32)> for Kernel {
     }
 }
 
-/// Row major image data
+// Row major image data
 
 // impl Image {
 //     pub fn new(width: u32, height: u32, value: Real) -> Self {
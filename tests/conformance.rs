@@ -0,0 +1,304 @@
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use gpca::{
+    dynamics::{
+        context::NodeRng,
+        implementations::{
+            brians_brain::BriansBrain,
+            cyclic::CyclicAutomaton,
+            greenberg_hastings::GreenbergHastings,
+            life::LifeLike,
+            neighborhood_table::NeighborhoodTable,
+            probabilistic_life::ProbabilisticLife,
+            totalistic::{OuterTotalisticTable, TotalisticTable},
+            wireworld::WireWorld,
+        },
+        local::LocalDynamic,
+    },
+    spaces::{
+        implementations::{
+            basic::{DiscreteState, HyperGraphHeap},
+            grid::{HEXAGONAL_NEIGHBORHOOD, MOORE_NEIGHBORHOOD, VON_NEUMANN_NEIGHBORHOOD},
+        },
+        lattice::LatticeComputable,
+        local::Stateable,
+    },
+    system::{
+        backend::{Backend, CpuRayon, CpuSerial, Gpu},
+        dynamical_system::DynamicalSystem,
+    },
+    third::{
+        codegen::{validate_wgsl, GridKernel},
+        wgpu::{select_device, ComputeDevice, GpuDevice},
+    },
+};
+
+type Space = HyperGraphHeap<DiscreteState, (), (u32, u32)>;
+
+// odd sides, so that wrapping around the borders is exercised in both directions
+const W: u32 = 37;
+const H: u32 = 23;
+
+const TRIALS: u64 = 4;
+const STEPS: usize = 40;
+
+fn random_generation(states: u32, seed: u64) -> Vec<DiscreteState> {
+    let mut rng = NodeRng::new(seed);
+
+    (0..W * H)
+        .map(|_| DiscreteState::from_state(rng.gen_range(states)))
+        .collect()
+}
+
+/// The rules with a WGSL kernel.
+fn kernel_rules() -> (
+    LifeLike,
+    CyclicAutomaton,
+    TotalisticTable,
+    OuterTotalisticTable,
+    NeighborhoodTable,
+) {
+    let mut rng = NodeRng::new(TRIALS);
+
+    (
+        LifeLike::new(&[3], &[2, 3]),
+        CyclicAutomaton::new(4, 2),
+        TotalisticTable::random(3, 8, &mut rng),
+        OuterTotalisticTable::random(3, 8, &mut rng),
+        // 3^9 entries, read from a storage buffer
+        NeighborhoodTable::random(3, 8, &mut rng),
+    )
+}
+
+/// Steps every backend from the same random generations and asserts they agree cell by cell
+/// after every step.
+fn check<D>(name: &str, dynamic: D, backends: &[&dyn Backend<Space, D, DiscreteState, ()>])
+where
+    D: LocalDynamic<DiscreteState, ()> + Debug + Clone,
+{
+    check_on(name, dynamic, &MOORE_NEIGHBORHOOD, backends)
+}
+
+/// `check` on a grid linked by `neighborhood`.
+fn check_on<D>(
+    name: &str,
+    dynamic: D,
+    neighborhood: &[(i32, i32)],
+    backends: &[&dyn Backend<Space, D, DiscreteState, ()>],
+) where
+    D: LocalDynamic<DiscreteState, ()> + Debug + Clone,
+{
+    for trial in 0..TRIALS {
+        let mem = random_generation(dynamic.states(), trial);
+
+        let mut systems = backends
+            .iter()
+            .map(|_| {
+                let space =
+                    HyperGraphHeap::new_grid_with_neighborhood(&mem, W, H, neighborhood, ());
+                let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic.clone()));
+                system.set_seed(trial);
+                system
+            })
+            .collect::<Vec<_>>();
+
+        for step in 1..=STEPS {
+            for (backend, system) in backends.iter().zip(systems.iter_mut()) {
+                system.step(*backend).unwrap();
+            }
+
+            let reference = systems[0].space_state();
+
+            for (backend, system) in backends.iter().zip(&systems).skip(1) {
+                assert!(
+                    system.space_state() == reference,
+                    "{name}: {:?} diverged from {:?} at step {step} of trial {trial}",
+                    backend.kind(),
+                    backends[0].kind(),
+                );
+            }
+        }
+    }
+}
+
+/// Runs a GPU session one generation per submission and asserts each generation read back
+/// matches the CPU.
+fn check_session<D>(name: &str, dynamic: D, device: &GpuDevice)
+where
+    D: LocalDynamic<DiscreteState, ()> + Debug + Clone,
+    DynamicalSystem<Space, D, DiscreteState, ()>: LatticeComputable<DiscreteState, ()>,
+{
+    let mem = random_generation(dynamic.states(), TRIALS);
+    let space = HyperGraphHeap::new_grid(&mem, W, H, ());
+
+    let mut cpu = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
+    let mut gpu = cpu.clone();
+
+    let mut session = gpu.gpu_session(device).unwrap();
+
+    for step in 1..=STEPS {
+        cpu.compute_sync();
+
+        session.run(1);
+        gpu.read_session(&session).unwrap();
+
+        assert!(
+            gpu.space_state() == cpu.space_state(),
+            "{name}: GPU session diverged from the CPU at step {step}",
+        );
+    }
+}
+
+/// Asserts naga accepts the kernels of `dynamic` for the Moore and von Neumann neighborhoods,
+/// which needs no adapter.
+fn check_wgsl(name: &str, dynamic: &impl GridKernel) {
+    for offsets in [&MOORE_NEIGHBORHOOD[..], &VON_NEUMANN_NEIGHBORHOOD[..]] {
+        if let Err(err) = dynamic
+            .kernel_wgsl(offsets)
+            .and_then(|wgsl| validate_wgsl(&wgsl))
+        {
+            panic!("{name}: {err}");
+        }
+    }
+}
+
+#[test]
+fn kernels_validate() {
+    let (life, cyclic, totalistic, outer, table) = kernel_rules();
+
+    check_wgsl("life", &life);
+    check_wgsl("cyclic", &cyclic);
+    check_wgsl("totalistic", &totalistic);
+    check_wgsl("outer totalistic", &outer);
+
+    // the full table is tied to its 8 neighbors
+    if let Err(err) = table
+        .kernel_wgsl(&MOORE_NEIGHBORHOOD)
+        .and_then(|wgsl| validate_wgsl(&wgsl))
+    {
+        panic!("neighborhood table: {err}");
+    }
+}
+
+#[test]
+fn cpu_backends_agree() {
+    let (life, cyclic, totalistic, outer, table) = kernel_rules();
+
+    check("life", life, &[&CpuSerial, &CpuRayon]);
+    check("cyclic", cyclic, &[&CpuSerial, &CpuRayon]);
+    check("totalistic", totalistic, &[&CpuSerial, &CpuRayon]);
+    check("outer totalistic", outer, &[&CpuSerial, &CpuRayon]);
+    check("neighborhood table", table, &[&CpuSerial, &CpuRayon]);
+
+    check("wireworld", WireWorld::new(), &[&CpuSerial, &CpuRayon]);
+    check(
+        "brian's brain",
+        BriansBrain::new(),
+        &[&CpuSerial, &CpuRayon],
+    );
+    check(
+        "greenberg-hastings",
+        GreenbergHastings::new(5, 2),
+        &[&CpuSerial, &CpuRayon],
+    );
+    check(
+        "probabilistic life",
+        ProbabilisticLife::new(&[3], &[2, 3], 0.8, 0.9),
+        &[&CpuSerial, &CpuRayon],
+    );
+}
+
+// A software adapter such as llvmpipe is enough; on headless Linux set `EGL_PLATFORM=surfaceless`.
+#[tokio::test]
+async fn gpu_agrees_with_the_cpu() {
+    let ComputeDevice::Gpu(device) = select_device().await else {
+        eprintln!("no GPU adapter, skipping");
+        return;
+    };
+
+    let gpu = Gpu::new(&device);
+    let (life, cyclic, totalistic, outer, table) = kernel_rules();
+
+    check("life", life.clone(), &[&CpuRayon, &gpu]);
+    check("cyclic", cyclic.clone(), &[&CpuRayon, &gpu]);
+    check("totalistic", totalistic.clone(), &[&CpuRayon, &gpu]);
+    check("outer totalistic", outer.clone(), &[&CpuRayon, &gpu]);
+    check("neighborhood table", table.clone(), &[&CpuRayon, &gpu]);
+
+    // the other layouts the kernels read their offsets for
+    let mut rng = NodeRng::new(TRIALS + 1);
+
+    check_on(
+        "von neumann totalistic",
+        TotalisticTable::random(3, 4, &mut rng),
+        &VON_NEUMANN_NEIGHBORHOOD,
+        &[&CpuRayon, &gpu],
+    );
+    check_on(
+        "hexagonal outer totalistic",
+        OuterTotalisticTable::random(3, 6, &mut rng),
+        &HEXAGONAL_NEIGHBORHOOD,
+        &[&CpuRayon, &gpu],
+    );
+    check_on(
+        "von neumann cyclic",
+        cyclic.clone(),
+        &VON_NEUMANN_NEIGHBORHOOD,
+        &[&CpuRayon, &gpu],
+    );
+
+    check_session("life", life, &device);
+    check_session("cyclic", cyclic, &device);
+    check_session("totalistic", totalistic, &device);
+    check_session("outer totalistic", outer, &device);
+    check_session("neighborhood table", table, &device);
+}
+
+#[tokio::test]
+async fn sessions_run_many_generations_per_submit() {
+    let ComputeDevice::Gpu(device) = select_device().await else {
+        eprintln!("no GPU adapter, skipping");
+        return;
+    };
+
+    let (life, ..) = kernel_rules();
+    let space = HyperGraphHeap::new_grid(&random_generation(2, 1), W, H, ());
+
+    let mut cpu = DynamicalSystem::new(Box::new(space), Box::new(life));
+    let mut gpu = cpu.clone();
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    gpu.observe(
+        move |generation: u64, nodes: &[DiscreteState], previous: &[DiscreteState]| {
+            log.lock()
+                .unwrap()
+                .push((generation, nodes.to_vec(), previous.to_vec()));
+        },
+    );
+
+    let mut session = gpu.gpu_session(&device).unwrap();
+
+    for _ in 0..STEPS - 1 {
+        cpu.compute_sync();
+    }
+
+    let before = cpu.space_state();
+    cpu.compute_sync();
+
+    session.run(STEPS as u32 - 7);
+    session.run(7);
+    gpu.read_session(&session).unwrap();
+
+    assert_eq!(gpu.generation(), STEPS as u64);
+    assert!(gpu.space_state() == cpu.space_state());
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].0, STEPS as u64);
+    assert!(seen[0].1 == cpu.space_state());
+    assert!(seen[0].2 == before);
+}
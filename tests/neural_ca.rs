@@ -0,0 +1,62 @@
+use gpca::{
+    dynamics::implementations::neural_ca::{NeuralCa, NeuralWeights, NpyArray},
+    error::Error,
+    spaces::implementations::{basic::HyperGraphHeap, neural::NeuralState},
+    system::dynamical_system::DynamicalSystem,
+};
+
+// fixture written by examples/data/neural_ca/generate.py
+const FIXTURE: &str = "examples/data/neural_ca";
+const STEPS: u64 = 6;
+
+#[test]
+fn inference_matches_the_reference_implementation() {
+    let weights = NeuralWeights::from_npy_dir(FIXTURE).unwrap();
+
+    // every cell fires so that the run is deterministic
+    let dynamic = NeuralCa::new(weights, 1.0);
+
+    let input = NpyArray::read(format!("{FIXTURE}/input.npy")).unwrap();
+    let expected = NpyArray::read(format!("{FIXTURE}/expected.npy")).unwrap();
+
+    let (h, w) = (input.shape[0] as u32, input.shape[1] as u32);
+
+    let mem = dynamic.grid_from_npy(&input).unwrap();
+    let space = HyperGraphHeap::new_grid(&mem, w, h, ());
+
+    let mut system = DynamicalSystem::new(Box::new(space), Box::new(dynamic));
+
+    for _ in 0..NeuralCa::PASSES * STEPS {
+        system.compute_sync();
+    }
+
+    assert_eq!(system.generation() / NeuralCa::PASSES, STEPS);
+
+    let error = system
+        .space_state()
+        .iter()
+        .flat_map(NeuralState::channels)
+        .zip(&expected.data)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, f32::max);
+
+    assert!(error < 1e-4, "max deviation from the reference {error:e}");
+}
+
+#[test]
+fn missing_or_malformed_weights_are_errors() {
+    assert!(matches!(
+        NeuralWeights::from_npy_dir("examples/data/missing"),
+        Err(Error::Io(_))
+    ));
+
+    assert!(matches!(
+        NpyArray::parse(b"not an npy file"),
+        Err(Error::Weights(_))
+    ));
+
+    assert!(matches!(
+        NeuralWeights::new(4, 1, vec![0.0; 11], vec![0.0], vec![0.0; 4], vec![0.0; 4]),
+        Err(Error::Weights(_))
+    ));
+}